@@ -0,0 +1,439 @@
+use anyhow::{Result, anyhow};
+use log::{info, warn};
+use serde_json::Value;
+use std::cell::RefCell;
+
+use crate::cache::ResponseCache;
+use crate::chunk::split_diff;
+use crate::config::ModelConfig;
+use crate::diff::Diff;
+use crate::findings::{FindingsReport, json_schema};
+use crate::profile::RenderedProfile;
+use crate::prompt::{AnalysisTarget, PromptBuilder};
+use crate::provider::{ApiError, CompletionRequest, DeltaSink, LlmProvider};
+use crate::relevance::{Prioritized, RelevanceScorer};
+use crate::retry::is_retryable;
+use crate::tokenizer::TokenCounter;
+
+/// 청크 분석 중간 응답의 최대 토큰 수
+const PARTIAL_RESPONSE_TOKENS: u32 = 2000;
+/// 청크 하나의 최소 토큰 수 (이보다 작으면 청크가 지나치게 많아지므로 분석하지 않음)
+const MIN_CHUNK_TOKENS: usize = 500;
+
+/// 분석 결과
+pub struct Analysis {
+    /// 마크다운 보고서 (첫 줄에 실제로 사용한 모델 표시, 예산 때문에 생략한 파일이 있으면 끝에 목록 첨부)
+    pub markdown: String,
+    /// 구조화 출력 모드의 발견 사항 (마크다운 보고서는 이것으로 작성됨)
+    pub findings: Option<FindingsReport>,
+}
+
+/// 선택한 제공자, 모델 체인, 프로필로 diff를 분석
+pub struct Analyzer {
+    provider: Box<dyn LlmProvider>,
+    /// 모델 체인 (첫 모델이 기본 모델, 실패하면 다음 모델로 재시도)
+    models: Vec<ModelConfig>,
+    profile: RenderedProfile,
+    /// 청크 분할 모드의 청크 최대 토큰 수 (None이면 큰 diff를 요약해서 분석)
+    chunk_tokens: Option<usize>,
+    /// 기본 모델 기준 토큰 계산기
+    tokens: TokenCounter,
+    /// 응답 캐시 (None이면 항상 API 호출)
+    cache: Option<ResponseCache>,
+    /// 최종 보고서를 JSON 스키마로 요청할지 여부
+    structured: bool,
+}
+
+impl Analyzer {
+    pub fn new(provider: Box<dyn LlmProvider>, models: Vec<ModelConfig>, profile: RenderedProfile, chunk_tokens: Option<usize>) -> Result<Self> {
+        let primary = models.first().ok_or_else(|| anyhow!("분석에 사용할 모델이 없습니다."))?;
+        let tokens = TokenCounter::for_model(&primary.name);
+        Ok(Self { provider, models, profile, chunk_tokens, tokens, cache: None, structured: false })
+    }
+
+    pub fn with_cache(mut self, cache: Option<ResponseCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    pub fn with_structured_output(mut self, structured: bool) -> Self {
+        self.structured = structured;
+        self
+    }
+
+    fn primary(&self) -> &ModelConfig {
+        &self.models[0]
+    }
+
+    /// 체인의 `index`번째 모델에 맞는 토큰 계산기
+    fn tokens_for(&self, index: usize) -> TokenCounter {
+        match index {
+            0 => self.tokens,
+            _ => TokenCounter::for_model(&self.models[index].name),
+        }
+    }
+
+    /// 분석 대상의 diff를 분석해 보고서를 반환
+    ///
+    /// `static_findings`는 정적 검사로 확인된 사실로, 프롬프트에 그대로 포함됨.
+    /// `stream`을 지정하면 최종 보고서 응답을 받는 대로 전달함 (청크 분석의 중간 응답은 제외).
+    /// 구조화 출력 모드에서는 응답을 스키마로 검증한 뒤 마크다운 보고서를 직접 작성함
+    pub async fn analyze(
+        &self,
+        target: AnalysisTarget<'_>,
+        diff: &Diff,
+        static_findings: Option<&str>,
+        stream: Option<DeltaSink<'_>>,
+    ) -> Result<Analysis> {
+        info!("{} API({})로 {} 분석 중...", self.provider.name(), self.primary().name, target.description());
+
+        let prompts = PromptBuilder::new(target, &self.profile)
+            .with_static_findings(static_findings)
+            .with_structured_output(self.structured);
+        let (content, models_used, dropped_files) = self.run(diff, &prompts, stream).await?;
+        let header = self.model_header(&models_used);
+        let with_dropped_files = |report: String| match &dropped_files {
+            Some(dropped_files) => format!("{}\n\n{}", report.trim_end(), dropped_files),
+            None => report,
+        };
+        if !self.structured {
+            return Ok(Analysis { markdown: with_dropped_files(format!("{}\n\n{}", header, content)), findings: None });
+        }
+
+        let findings = FindingsReport::parse(&content)?;
+        let markdown = format!("{}\n\n{}", header, findings.render_markdown(prompts.target(), &self.profile));
+        Ok(Analysis { markdown: with_dropped_files(markdown), findings: Some(findings) })
+    }
+
+    /// 최종 보고서 요청에 붙일 JSON 스키마 (구조화 출력 모드에서만)
+    fn final_schema(&self) -> Option<Value> {
+        self.structured.then(json_schema)
+    }
+
+    /// 보고서 머리의 분석 모델 표시
+    fn model_header(&self, models_used: &[String]) -> String {
+        let primary = &self.primary().name;
+        if models_used.iter().all(|model| model == primary) {
+            format!("> 분석 모델: {} ({})", primary, self.provider.name())
+        } else {
+            format!(
+                "> 분석 모델: {} ({}, 기본 모델 {} 대신 대체 모델 사용)",
+                models_used.join(", "),
+                self.provider.name(),
+                primary
+            )
+        }
+    }
+
+    /// 단일 프롬프트 완성 요청 (캐시에 같은 요청의 응답이 있으면 API를 호출하지 않음)
+    async fn complete(
+        &self,
+        model: &str,
+        prompt: String,
+        max_tokens: u32,
+        json_schema: Option<Value>,
+        stream: Option<DeltaSink<'_>>,
+    ) -> Result<String> {
+        let request = CompletionRequest {
+            model: model.to_string(),
+            prompt,
+            max_tokens,
+            temperature: 0.3,
+            json_schema,
+        };
+
+        let key = ResponseCache::key(self.provider.name(), &request);
+        if let Some(content) = self.cache.as_ref().and_then(|cache| cache.get(&key)) {
+            info!("캐시된 응답을 사용합니다 ({}, 키 {})", model, &key[..12]);
+            if let Some(on_delta) = stream {
+                on_delta(&content);
+            }
+            return Ok(content);
+        }
+
+        let content = match stream {
+            Some(on_delta) => self.provider.complete_stream(&request, on_delta).await?,
+            None => self.provider.complete(&request).await?,
+        };
+        let Some(cache) = &self.cache else {
+            return Ok(content);
+        };
+        // 캐시 저장 실패는 분석 결과에 영향이 없으므로 경고만 출력
+        if let Err(error) = cache.put(&key, self.provider.name(), model, &content) {
+            warn!("응답 캐시 저장 실패: {}", error);
+        }
+        Ok(content)
+    }
+
+    /// 모델 체인 순서대로 요청하고, 응답과 실제로 사용한 모델을 반환
+    ///
+    /// 컨텍스트 초과, rate limit, 서비스 장애로 실패하면 다음 모델로 넘어감. `build_prompt`가
+    /// None을 반환하면 (프롬프트가 컨텍스트에 들어가지 않으면) 그 모델은 건너뜀.
+    /// 스트리밍 중 응답 일부를 이미 전달했다면 두 모델의 응답이 섞이지 않도록 넘어가지 않고 실패함
+    async fn complete_with_fallback(
+        &self,
+        max_tokens: u32,
+        json_schema: Option<Value>,
+        mut stream: Option<DeltaSink<'_>>,
+        build_prompt: impl Fn(&ModelConfig, TokenCounter) -> Option<String>,
+    ) -> Result<(String, String)> {
+        let mut last_error = None;
+        for (index, model) in self.models.iter().enumerate() {
+            let Some(prompt) = build_prompt(model, self.tokens_for(index)) else {
+                info!("{}의 컨텍스트에 프롬프트가 들어가지 않아 건너뜁니다.", model.name);
+                continue;
+            };
+
+            let max_tokens = max_tokens.min(model.max_output_tokens);
+            let mut streamed = false;
+            let result = match stream.as_mut() {
+                Some(on_delta) => {
+                    let mut forward = |delta: &str| {
+                        streamed = true;
+                        on_delta(delta);
+                    };
+                    self.complete(&model.name, prompt, max_tokens, json_schema.clone(), Some(&mut forward)).await
+                }
+                None => self.complete(&model.name, prompt, max_tokens, json_schema.clone(), None).await,
+            };
+            let error = match result {
+                Ok(content) => {
+                    if index > 0 {
+                        info!("대체 모델 {}로 분석 완료!", model.name);
+                    }
+                    return Ok((content, model.name.clone()));
+                }
+                Err(error) => error,
+            };
+
+            let Some(reason) = fallback_reason(&error) else {
+                return Err(error);
+            };
+            if streamed {
+                warn!("\n{} 응답 스트리밍 중 실패했습니다 ({}). 받은 응답 일부만 남깁니다.", model.name, reason);
+                return Err(error);
+            }
+            if let Some(next) = self.models.get(index + 1) {
+                warn!("{} 요청 실패 ({}). {}로 재시도합니다...", model.name, reason, next.name);
+            }
+            last_error = Some(error);
+        }
+        Err(last_error.unwrap_or_else(|| anyhow!("프롬프트가 컨텍스트에 들어가는 모델이 없습니다.")))
+    }
+
+    /// 모델에 보낼 수 있는 diff 내용의 최대 토큰 수 (컨텍스트와 모델별 예산 중 작은 값)
+    fn content_budget(model: &ModelConfig, tokens: TokenCounter, prompts: &PromptBuilder<'_>) -> usize {
+        // diff를 제외한 프롬프트 토큰 수
+        let prompt_base_tokens = tokens.count(&prompts.analysis_prompt(""));
+        let max_content_tokens = model
+            .context_window
+            .saturating_sub(prompt_base_tokens + model.max_output_tokens as usize);
+        model.budget.map_or(max_content_tokens, |budget| budget.min(max_content_tokens))
+    }
+
+    /// 응답, 사용한 모델, 예산 때문에 생략한 파일 보고서(요약해서 분석했을 때만)를 반환
+    async fn run(
+        &self,
+        diff: &Diff,
+        prompts: &PromptBuilder<'_>,
+        stream: Option<DeltaSink<'_>>,
+    ) -> Result<(String, Vec<String>, Option<String>)> {
+        // diff 내용 처리
+        let diff_content = diff.render();
+
+        // 청크 분할 모드에서는 자르지 않고 나누어 분석
+        if let Some(chunk_tokens) = self.chunk_tokens {
+            let chunk_tokens = chunk_tokens.min(Self::content_budget(self.primary(), self.tokens, prompts));
+            if self.tokens.count(&diff_content) > chunk_tokens {
+                if chunk_tokens < MIN_CHUNK_TOKENS {
+                    return Err(anyhow!(
+                        "청크에 넣을 수 있는 diff가 {} 토큰뿐입니다 (최소 {} 토큰). --chunk-tokens 값이나 {}의 context_window, max_output_tokens 설정을 확인해주세요.",
+                        chunk_tokens,
+                        MIN_CHUNK_TOKENS,
+                        self.primary().name
+                    ));
+                }
+                let (content, models_used) = self.analyze_in_chunks(diff, prompts, chunk_tokens, stream).await?;
+                return Ok((content, models_used, None));
+            }
+        }
+
+        // 마지막으로 만든 프롬프트(응답을 받은 모델의 프롬프트)에서 생략한 파일 보고서
+        let dropped_files = RefCell::new(None);
+
+        // 모델마다 예산에 맞춰 diff 내용을 다시 준비
+        let build_prompt = |model: &ModelConfig, tokens: TokenCounter| {
+            let max_content_tokens = Self::content_budget(model, tokens, prompts);
+            *dropped_files.borrow_mut() = None;
+            let analysis_content = if tokens.count(&diff_content) > max_content_tokens {
+                info!("Diff 내용이 {}의 예산보다 큽니다. 관련도가 높은 파일부터 요약해서 분석합니다...", model.name);
+                let (summary, selection) = smart_summarize_diff(diff, max_content_tokens, tokens, &self.profile.relevance_keywords);
+                if !selection.is_complete() {
+                    let omitted: Vec<&str> = selection.partial.iter().chain(&selection.dropped).map(|file| file.path.as_str()).collect();
+                    info!("파일 {}개를 포함하고 {}개는 내용을 생략했습니다: {}", selection.included, omitted.len(), omitted.join(", "));
+                    *dropped_files.borrow_mut() = Some(selection.markdown(&model.name));
+                }
+                summary
+            } else {
+                diff_content.clone()
+            };
+
+            let prompt = prompts.analysis_prompt(&analysis_content);
+            info!(
+                "예상 토큰 사용량: {} / {} ({}, {})",
+                tokens.count(&prompt),
+                format_tokens(model.context_window),
+                model.name,
+                tokens.label()
+            );
+            Some(prompt)
+        };
+
+        let (content, model) = self.complete_with_fallback(u32::MAX, self.final_schema(), stream, build_prompt).await?;
+        Ok((content, vec![model], dropped_files.into_inner()))
+    }
+
+    /// 청크별 중간 분석(map) 후 최종 보고서로 종합(reduce)
+    ///
+    /// 청크는 기본 모델 기준으로 나누므로, 대체 모델은 프롬프트가 컨텍스트에 들어갈 때만 사용함
+    async fn analyze_in_chunks(
+        &self,
+        diff: &Diff,
+        prompts: &PromptBuilder<'_>,
+        chunk_tokens: usize,
+        stream: Option<DeltaSink<'_>>,
+    ) -> Result<(String, Vec<String>)> {
+        let chunks = split_diff(diff, chunk_tokens, self.tokens);
+        let total = chunks.len();
+        info!("Diff 내용이 큽니다. {}개 청크로 나누어 분석합니다...", total);
+
+        let mut models_used: Vec<String> = Vec::new();
+        let mut record_model = |model: String| {
+            if !models_used.contains(&model) {
+                models_used.push(model);
+            }
+        };
+
+        let mut partial_reports = Vec::with_capacity(total);
+        for (index, chunk) in chunks.iter().enumerate() {
+            let chunk_content = chunk.render();
+            info!("청크 {}/{} 분석 중... (파일 {}개, 예상 토큰 {})", index + 1, total, chunk.files.len(), self.tokens.count(&chunk_content));
+
+            let prompt = prompts.chunk_analysis_prompt(index + 1, total, &chunk_content);
+            let (report, model) = self
+                .complete_with_fallback(PARTIAL_RESPONSE_TOKENS, None, None, |model, tokens| fit_prompt(&prompt, model, tokens, PARTIAL_RESPONSE_TOKENS))
+                .await?;
+            record_model(model);
+            partial_reports.push(report);
+        }
+
+        // 중간 결과가 너무 많으면 여러 단계로 나누어 합침
+        while partial_reports.len() > 1 && self.tokens.count(&partial_reports.join("\n\n")) > chunk_tokens {
+            let groups = group_by_tokens(&partial_reports, chunk_tokens, self.tokens);
+            info!("중간 분석 결과 {}개를 {}개로 합치는 중...", partial_reports.len(), groups.len());
+
+            let mut merged_reports = Vec::with_capacity(groups.len());
+            for group in groups {
+                if group.len() == 1 {
+                    merged_reports.extend(group);
+                    continue;
+                }
+                let prompt = prompts.merge_prompt(&group);
+                let (report, model) = self
+                    .complete_with_fallback(PARTIAL_RESPONSE_TOKENS, None, None, |model, tokens| fit_prompt(&prompt, model, tokens, PARTIAL_RESPONSE_TOKENS))
+                    .await?;
+                record_model(model);
+                merged_reports.push(report);
+            }
+            partial_reports = merged_reports;
+        }
+
+        info!("부분 분석 결과 {}개를 종합해 최종 보고서를 작성합니다...", partial_reports.len());
+        let prompt = prompts.reduce_prompt(diff, total, &partial_reports);
+        let (report, model) = self
+            .complete_with_fallback(u32::MAX, self.final_schema(), stream, |model, tokens| fit_prompt(&prompt, model, tokens, model.max_output_tokens))
+            .await?;
+        record_model(model);
+        Ok((report, models_used))
+    }
+}
+
+/// 다음 모델로 넘어갈 실패인지 판단하고 사유를 반환
+fn fallback_reason(error: &anyhow::Error) -> Option<&'static str> {
+    let api_error = error.downcast_ref::<ApiError>();
+    if api_error.is_some_and(|api_error| api_error.is_context_length_exceeded()) {
+        return Some("컨텍스트 길이 초과");
+    }
+    if !is_retryable(error) {
+        return None;
+    }
+    match api_error {
+        Some(api_error) if api_error.status == 429 => Some("rate limit"),
+        _ => Some("서비스 장애"),
+    }
+}
+
+/// 프롬프트와 응답이 모델 컨텍스트에 들어가면 프롬프트를 그대로 사용
+fn fit_prompt(prompt: &str, model: &ModelConfig, tokens: TokenCounter, max_tokens: u32) -> Option<String> {
+    let required = tokens.count(prompt) + max_tokens.min(model.max_output_tokens) as usize;
+    (required <= model.context_window).then(|| prompt.to_string())
+}
+
+/// 중간 결과들을 토큰 예산 안에서 연속된 그룹으로 묶음 (진행을 위해 최소 2개씩)
+fn group_by_tokens(reports: &[String], max_tokens: usize, counter: TokenCounter) -> Vec<Vec<String>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    let mut group_tokens = 0;
+
+    for report in reports {
+        let tokens = counter.count(report);
+        match groups.last_mut() {
+            Some(group) if group.len() < 2 || group_tokens + tokens <= max_tokens => {
+                group.push(report.clone());
+                group_tokens += tokens;
+            }
+            _ => {
+                groups.push(vec![report.clone()]);
+                group_tokens = tokens;
+            }
+        }
+    }
+    groups
+}
+
+/// 예산보다 큰 diff를 통계, 전체 파일 목록, 관련도 순으로 고른 파일 내용으로 요약
+///
+/// 예산에 들어가지 않는 파일은 생략 파일 목록으로 프롬프트에 명시함
+fn smart_summarize_diff(diff: &Diff, max_tokens: usize, tokens: TokenCounter, keywords: &[String]) -> (String, Prioritized) {
+    let mut summary = String::new();
+
+    // 기본 통계
+    summary.push_str(&format!("=== 통계 ===\n{}\n\n", diff.stats_line()));
+
+    // 내용이 잘리더라도 변경된 파일 목록은 모두 포함
+    summary.push_str(&format!("=== 변경 파일 ===\n{}\n", diff.file_list()));
+
+    // 남은 예산 안에서 관련도가 높은 파일부터 포함 (소스 > 빌드 설정 > 테스트 > 문서)
+    let available_tokens = max_tokens.saturating_sub(tokens.count(&summary));
+    let ranked = RelevanceScorer::new(keywords).rank(diff);
+    let selection = Prioritized::select(diff, ranked, available_tokens, tokens);
+
+    if !selection.is_complete() {
+        summary.push_str(&selection.prompt_section());
+        summary.push('\n');
+    }
+    summary.push_str(&selection.content);
+    (summary, selection)
+}
+
+/// 천 단위 구분 기호를 넣은 토큰 수 (128,000)
+fn format_tokens(count: usize) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
@@ -0,0 +1,806 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tree_sitter::{Language, Node, Parser};
+
+use crate::diff::Diff;
+use crate::repo::RepoBackend;
+use crate::source::DiffSource;
+
+/// 시그니처 표시 최대 글자 수
+const MAX_SIGNATURE_CHARS: usize = 160;
+/// 보고서와 프롬프트에 나열할 최대 변경 수 (나머지는 개수만 표시)
+const MAX_LISTED_CHANGES: usize = 200;
+
+/// 내보낸 심볼 종류
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SymbolKind {
+    Function,
+    Class,
+    Method,
+    Property,
+    Variable,
+    Interface,
+    Type,
+    Enum,
+    Namespace,
+    ReExport,
+}
+
+impl SymbolKind {
+    fn label(self) -> &'static str {
+        match self {
+            SymbolKind::Function => "함수",
+            SymbolKind::Class => "클래스",
+            SymbolKind::Method => "메서드",
+            SymbolKind::Property => "속성",
+            SymbolKind::Variable => "변수",
+            SymbolKind::Interface => "인터페이스",
+            SymbolKind::Type => "타입",
+            SymbolKind::Enum => "열거형",
+            SymbolKind::Namespace => "네임스페이스",
+            SymbolKind::ReExport => "재내보내기",
+        }
+    }
+}
+
+/// 내보낸 심볼 하나
+#[derive(Clone)]
+struct Symbol {
+    kind: SymbolKind,
+    /// 공백을 정리한 선언부 (함수 본문, 필드 초기값 등은 제외)
+    signature: String,
+}
+
+/// 파일 하나의 내보낸 심볼 (클래스·인터페이스·객체 멤버는 `Player.play`처럼 이름을 붙임)
+type Exports = BTreeMap<String, Symbol>;
+
+/// 심볼 변경 종류 (사용자 코드를 깨뜨릴 수 있는 순서)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Removed,
+    Changed,
+    Added,
+}
+
+impl ChangeKind {
+    fn label(self) -> &'static str {
+        match self {
+            ChangeKind::Removed => "➖ 삭제",
+            ChangeKind::Changed => "✏️ 변경",
+            ChangeKind::Added => "➕ 추가",
+        }
+    }
+}
+
+/// 내보낸 심볼 하나의 변경
+pub struct ApiChange {
+    pub change: ChangeKind,
+    pub path: String,
+    pub name: String,
+    kind: SymbolKind,
+    old_signature: Option<String>,
+    new_signature: Option<String>,
+}
+
+impl ApiChange {
+    /// 변경 전후 시그니처 (`span`으로 인라인 코드 작성)
+    fn signature(&self, span: fn(&str) -> String) -> String {
+        match (&self.old_signature, &self.new_signature) {
+            (Some(old), Some(new)) => format!("{} → {}", span(old), span(new)),
+            (Some(signature), None) | (None, Some(signature)) => span(signature),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// 두 리비전의 JS/TS 내보내기(export) 비교 결과
+pub struct ApiSurfaceDelta {
+    from: String,
+    to: String,
+    /// 비교한 JS/TS 파일 수
+    pub files: usize,
+    pub changes: Vec<ApiChange>,
+}
+
+/// diff에 포함된 JS/TS 파일의 내보낸 심볼을 변경 전후 리비전에서 추출해 비교
+///
+/// 리비전 범위와 커밋 분석에서만 비교하며, 스테이징/작업 트리 분석이거나 JS/TS 파일 변경이 없으면 None
+pub async fn compare(repo: &dyn RepoBackend, source: &DiffSource, diff: &Diff) -> Result<Option<ApiSurfaceDelta>> {
+    let (old_rev, new_rev) = match source {
+        DiffSource::Range { from, to, merge_base: false } => (from.clone(), to.clone()),
+        DiffSource::Range { from, to, merge_base: true } => (repo.merge_base(from, to).await?, to.clone()),
+        DiffSource::Commit(commit) => (format!("{}^", commit), commit.clone()),
+        DiffSource::Staged | DiffSource::Worktree => return Ok(None),
+    };
+    // 루트 커밋은 이전 리비전이 없으므로 모든 심볼을 추가된 것으로 봄
+    let old_rev = repo.resolves_to_commit(&old_rev).await?.then_some(old_rev);
+
+    let mut files = 0;
+    let mut changes = Vec::new();
+    for file in diff.files.iter().filter(|file| !file.binary) {
+        let old = match (&old_rev, &file.old_path) {
+            (Some(rev), Some(path)) => exports_at(repo, rev, path).await?,
+            _ => None,
+        };
+        let new = match &file.new_path {
+            Some(path) => exports_at(repo, &new_rev, path).await?,
+            None => None,
+        };
+        if old.is_none() && new.is_none() {
+            continue;
+        }
+        files += 1;
+        changes.extend(diff_exports(&file.path().display().to_string(), old.unwrap_or_default(), new.unwrap_or_default()));
+    }
+    if files == 0 {
+        return Ok(None);
+    }
+
+    changes.sort_by(|a, b| (a.change, &a.path, &a.name).cmp(&(b.change, &b.path, &b.name)));
+    let from = old_rev.map_or_else(|| "(없음)".to_string(), |rev| short_rev(&rev));
+    Ok(Some(ApiSurfaceDelta { from, to: short_rev(&new_rev), files, changes }))
+}
+
+impl ApiSurfaceDelta {
+    pub fn count(&self, change: ChangeKind) -> usize {
+        self.changes.iter().filter(|item| item.change == change).count()
+    }
+
+    fn counts_line(&self) -> String {
+        format!(
+            "추가 {}개, 삭제 {}개, 변경 {}개",
+            self.count(ChangeKind::Added),
+            self.count(ChangeKind::Removed),
+            self.count(ChangeKind::Changed)
+        )
+    }
+
+    /// 원본 diff보다 앞에 프롬프트에 포함할 변경 요약
+    pub fn prompt_section(&self) -> String {
+        let mut section = format!(
+            "**공개 API 변경 요약 ({} → {}, 변경된 JS/TS 파일 {}개의 export를 구문 분석해 비교):**\n",
+            self.from, self.to, self.files
+        );
+        if self.changes.is_empty() {
+            section.push_str("내보낸 심볼의 추가, 삭제, 시그니처 변경이 없습니다. 내부 동작 변경만 diff에서 확인하세요.\n");
+            return section;
+        }
+
+        section.push_str(&format!(
+            "{}. 아래 항목은 코드에서 직접 추출한 사실입니다. 삭제되거나 시그니처가 바뀐 심볼은 라이브러리 사용자 코드를 깨뜨릴 수 있으므로 보고서의 API 변경 사항에 빠짐없이 반영하고, diff에서 영향과 대체 방법을 확인하세요.\n",
+            self.counts_line()
+        ));
+        for item in self.changes.iter().take(MAX_LISTED_CHANGES) {
+            section.push_str(&format!(
+                "- {} `{}` `{}` ({}): {}\n",
+                item.change.label(),
+                item.path,
+                item.name,
+                item.kind.label(),
+                item.signature(code_span)
+            ));
+        }
+        section.push_str(&self.omitted_line());
+        section
+    }
+
+    /// 분석 보고서 끝에 붙이는 마크다운 섹션
+    pub fn markdown(&self) -> String {
+        let mut report = format!("## 🧩 공개 API 변경 ({} → {})\n\n", self.from, self.to);
+        if self.changes.is_empty() {
+            report.push_str(&format!(
+                "변경된 JS/TS 파일 {}개에서 내보낸 심볼의 추가, 삭제, 시그니처 변경이 없습니다.\n",
+                self.files
+            ));
+            return report;
+        }
+
+        report.push_str(&format!("변경된 JS/TS 파일 {}개의 export 비교: {}\n\n", self.files, self.counts_line()));
+        report.push_str("| 변경 | 심볼 | 종류 | 파일 | 시그니처 |\n|---|---|---|---|---|\n");
+        for item in self.changes.iter().take(MAX_LISTED_CHANGES) {
+            report.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                item.change.label(),
+                table_code_span(&item.name),
+                item.kind.label(),
+                table_code_span(&item.path),
+                item.signature(table_code_span)
+            ));
+        }
+        report.push_str(&self.omitted_line());
+        report
+    }
+
+    fn omitted_line(&self) -> String {
+        match self.changes.len().checked_sub(MAX_LISTED_CHANGES) {
+            Some(omitted) if omitted > 0 => format!("\n외 {}개 변경은 생략했습니다.\n", omitted),
+            _ => String::new(),
+        }
+    }
+}
+
+/// 리비전 시점 파일의 내보낸 심볼 (JS/TS 파일이 아니거나 그 시점에 파일이 없으면 None)
+async fn exports_at(repo: &dyn RepoBackend, rev: &str, path: &Path) -> Result<Option<Exports>> {
+    let Some(language) = language_for(path) else {
+        return Ok(None);
+    };
+    let Some(content) = repo.read_file(rev, path).await? else {
+        return Ok(None);
+    };
+    parse_exports(&String::from_utf8_lossy(&content), &language)
+}
+
+fn parse_exports(source: &str, language: &Language) -> Result<Option<Exports>> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(language)
+        .context("tree-sitter 문법을 불러올 수 없습니다")?;
+    // 문법 오류가 있어도 tree-sitter는 나머지 부분을 파싱하므로 추출할 수 있는 만큼 추출
+    let Some(tree) = parser.parse(source.as_bytes(), None) else {
+        return Ok(None);
+    };
+    Ok(Some(ExportExtractor::new(source).extract(tree.root_node())))
+}
+
+/// 표시용 리비전 (전체 커밋 SHA는 앞 10자리로 줄임)
+fn short_rev(rev: &str) -> String {
+    let (sha, suffix) = rev.split_at(rev.find('^').unwrap_or(rev.len()));
+    match sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit()) {
+        true => format!("{}{}", &sha[..10], suffix),
+        false => rev.to_string(),
+    }
+}
+
+fn language_for(path: &Path) -> Option<Language> {
+    let extension = path.extension()?.to_str()?;
+    let language = match extension {
+        "js" | "mjs" | "cjs" | "jsx" => tree_sitter_javascript::LANGUAGE,
+        "ts" | "mts" | "cts" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
+        "tsx" => tree_sitter_typescript::LANGUAGE_TSX,
+        _ => return None,
+    };
+    Some(language.into())
+}
+
+fn diff_exports(path: &str, mut old: Exports, new: Exports) -> Vec<ApiChange> {
+    let mut changes = Vec::new();
+    let change = |change, name: &str, kind, old_signature, new_signature| ApiChange {
+        change,
+        path: path.to_string(),
+        name: name.to_string(),
+        kind,
+        old_signature,
+        new_signature,
+    };
+
+    for (name, symbol) in new {
+        match old.remove(&name) {
+            None => changes.push(change(ChangeKind::Added, &name, symbol.kind, None, Some(symbol.signature))),
+            Some(previous) if previous.signature != symbol.signature => changes.push(change(
+                ChangeKind::Changed,
+                &name,
+                symbol.kind,
+                Some(previous.signature),
+                Some(symbol.signature),
+            )),
+            Some(_) => {}
+        }
+    }
+    for (name, symbol) in old {
+        changes.push(change(ChangeKind::Removed, &name, symbol.kind, Some(symbol.signature), None));
+    }
+    changes
+}
+
+/// 파일 최상위의 ES 모듈 export와 CommonJS `module.exports`/`exports.x` 할당에서 심볼 추출
+struct ExportExtractor<'a> {
+    source: &'a str,
+    exports: Exports,
+    /// 최상위에 선언한 모든 심볼 (`export { name }`, `module.exports = { name }`처럼 이름으로 내보낼 때 사용)
+    declared: Vec<(String, Symbol)>,
+}
+
+impl<'a> ExportExtractor<'a> {
+    fn new(source: &'a str) -> Self {
+        Self { source, exports: Exports::new(), declared: Vec::new() }
+    }
+
+    fn extract(mut self, root: Node) -> Exports {
+        // 선언보다 앞에서 이름으로 내보낼 수 있으므로 선언과 선언 export를 먼저 모두 수집
+        let mut cursor = root.walk();
+        let nodes: Vec<Node> = root.named_children(&mut cursor).collect();
+        let mut name_exports = Vec::new();
+        for node in nodes {
+            let declaration = match node.kind() {
+                "export_statement" => node.child_by_field_name("declaration"),
+                _ => Some(node),
+            };
+            if let Some(declaration) = declaration {
+                let symbols = self.declaration_symbols(declaration);
+                self.declared.extend(symbols);
+            }
+            match node.kind() {
+                "export_statement" if declaration.is_some() => self.export_statement(node),
+                "export_statement" | "expression_statement" => name_exports.push(node),
+                _ => {}
+            }
+        }
+
+        for node in name_exports {
+            match node.kind() {
+                "export_statement" => self.export_statement(node),
+                _ => self.commonjs_export(node),
+            }
+        }
+        self.exports
+    }
+
+    /// 이름으로 내보낸 심볼 (선언을 찾을 수 없으면 import한 심볼을 다시 내보내는 것으로 봄)
+    fn named_export(&self, local: &str, exported: &str) -> Vec<(String, Symbol)> {
+        // 이미 선언과 함께 내보낸 심볼은 멤버를 중복하지 않고 별칭만 추가
+        if self.exports.contains_key(local) {
+            if local == exported {
+                return Vec::new();
+            }
+            let signature = format!("export {{ {} as {} }}", local, exported);
+            return vec![(exported.to_string(), Symbol { kind: SymbolKind::ReExport, signature })];
+        }
+        let symbols: Vec<_> = self
+            .declared
+            .iter()
+            .filter_map(|(name, symbol)| Some((rename(name, local, exported)?, symbol.clone())))
+            .collect();
+        if !symbols.is_empty() {
+            return symbols;
+        }
+        let signature = match local == exported {
+            true => format!("export {{ {} }}", local),
+            false => format!("export {{ {} as {} }}", local, exported),
+        };
+        vec![(exported.to_string(), Symbol { kind: SymbolKind::ReExport, signature })]
+    }
+
+    fn export_statement(&mut self, node: Node) {
+        let mut cursor = node.walk();
+        let is_default = node.children(&mut cursor).any(|child| child.kind() == "default");
+
+        if let Some(declaration) = node.child_by_field_name("declaration") {
+            let mut symbols = self.declaration_symbols(declaration);
+            if is_default {
+                symbols = default_export(symbols);
+            }
+            self.exports.extend(symbols);
+            return;
+        }
+
+        if let Some(value) = node.child_by_field_name("value") {
+            match value.kind() {
+                "identifier" => {
+                    let symbols = self.named_export(self.text(value), "default");
+                    self.exports.extend(symbols);
+                }
+                _ => {
+                    let symbols = self.value_symbols("default", value, "export default");
+                    self.exports.extend(symbols);
+                }
+            }
+            return;
+        }
+
+        let source = node.child_by_field_name("source").map(|source| self.text(source).to_string());
+        let mut cursor = node.walk();
+        let clause = node.named_children(&mut cursor).find(|child| child.kind() == "export_clause");
+        if let Some(clause) = clause {
+            let mut cursor = clause.walk();
+            for specifier in clause.named_children(&mut cursor).filter(|child| child.kind() == "export_specifier") {
+                let Some(name) = specifier.child_by_field_name("name").map(|name| self.text(name).to_string()) else {
+                    continue;
+                };
+                let exported = specifier
+                    .child_by_field_name("alias")
+                    .map(|alias| self.text(alias).to_string())
+                    .unwrap_or_else(|| name.clone());
+                match &source {
+                    Some(source) => {
+                        let signature = normalize(&format!("export {{ {} }} from {}", self.text(specifier), source));
+                        self.exports.insert(exported, Symbol { kind: SymbolKind::ReExport, signature });
+                    }
+                    None => {
+                        let symbols = self.named_export(&name, &exported);
+                        self.exports.extend(symbols);
+                    }
+                }
+            }
+            return;
+        }
+
+        // `export * from './x'`, `export * as ns from './x'`
+        if let Some(source) = source {
+            let mut cursor = node.walk();
+            let namespace = node.named_children(&mut cursor).find(|child| child.kind() == "namespace_export");
+            let name = match namespace.and_then(|namespace| namespace.named_child(0)) {
+                Some(alias) => self.text(alias).to_string(),
+                None => format!("* from {}", source),
+            };
+            let signature = normalize(self.text(node).trim_end_matches(';'));
+            self.exports.insert(name, Symbol { kind: SymbolKind::ReExport, signature });
+        }
+    }
+
+    /// `module.exports = ...`, `module.exports.x = ...`, `exports.x = ...`
+    fn commonjs_export(&mut self, node: Node) {
+        let Some(assignment) = node.named_child(0).filter(|child| child.kind() == "assignment_expression") else {
+            return;
+        };
+        let (Some(left), Some(right)) = (assignment.child_by_field_name("left"), assignment.child_by_field_name("right")) else {
+            return;
+        };
+        let target: String = self.text(left).split_whitespace().collect();
+
+        if target == "module.exports" {
+            match right.kind() {
+                // 객체의 각 속성을 개별 export로 봄
+                "object" => {
+                    let symbols = self.object_members(None, right);
+                    self.exports.extend(symbols);
+                }
+                "identifier" => {
+                    let symbols = self.named_export(self.text(right), "module.exports");
+                    self.exports.extend(symbols);
+                }
+                _ => {
+                    let symbols = self.value_symbols("module.exports", right, "module.exports =");
+                    self.exports.extend(symbols);
+                }
+            }
+            return;
+        }
+
+        let name = target
+            .strip_prefix("module.exports.")
+            .or_else(|| target.strip_prefix("exports."))
+            .filter(|name| !name.contains('.'));
+        if let Some(name) = name {
+            let symbols = self.value_symbols(name, right, &format!("exports.{} =", name));
+            self.exports.extend(symbols);
+        }
+    }
+
+    fn declaration_symbols(&self, node: Node) -> Vec<(String, Symbol)> {
+        let name = node.child_by_field_name("name").map(|name| self.text(name).to_string());
+        match (node.kind(), name) {
+            ("function_declaration" | "generator_function_declaration" | "function_signature", Some(name)) => {
+                vec![(name, self.symbol(SymbolKind::Function, self.header(node)))]
+            }
+            ("class_declaration" | "abstract_class_declaration", Some(name)) => self.class_symbols(&name, node, String::new()),
+            ("interface_declaration", Some(name)) => {
+                let mut symbols = vec![(name.clone(), self.symbol(SymbolKind::Interface, self.header(node)))];
+                if let Some(body) = node.child_by_field_name("body") {
+                    symbols.extend(self.type_members(&name, body));
+                }
+                symbols
+            }
+            ("type_alias_declaration", Some(name)) => {
+                vec![(name, self.symbol(SymbolKind::Type, self.text(node).trim_end_matches(';').to_string()))]
+            }
+            ("enum_declaration", Some(name)) => {
+                let mut symbols = vec![(name.clone(), self.symbol(SymbolKind::Enum, self.header(node)))];
+                if let Some(body) = node.child_by_field_name("body") {
+                    let mut cursor = body.walk();
+                    for member in body.named_children(&mut cursor) {
+                        let member_name = member.child_by_field_name("name").unwrap_or(member);
+                        let key = format!("{}.{}", name, self.text(member_name));
+                        symbols.push((key, self.symbol(SymbolKind::Property, self.text(member).to_string())));
+                    }
+                }
+                symbols
+            }
+            ("internal_module" | "module", Some(name)) => {
+                vec![(name, self.symbol(SymbolKind::Namespace, self.header(node)))]
+            }
+            ("lexical_declaration" | "variable_declaration", _) => {
+                let keyword = node.child(0).map(|keyword| self.text(keyword)).unwrap_or("var");
+                let mut symbols = Vec::new();
+                let mut cursor = node.walk();
+                for declarator in node.named_children(&mut cursor).filter(|child| child.kind() == "variable_declarator") {
+                    // 구조 분해로 선언한 이름은 추적하지 않음
+                    let Some(name) = declarator.child_by_field_name("name").filter(|name| name.kind() == "identifier") else {
+                        continue;
+                    };
+                    let name = self.text(name);
+                    symbols.extend(match declarator.child_by_field_name("value") {
+                        Some(value) if is_function_or_class(value) || value.kind() == "object" => {
+                            self.value_symbols(name, value, &format!("{} {} =", keyword, name))
+                        }
+                        // 일반 값은 바뀌어도 API 변경으로 보지 않고 타입 표기만 비교
+                        _ => {
+                            let annotation = declarator.child_by_field_name("type").map(|kind| self.text(kind)).unwrap_or_default();
+                            vec![(name.to_string(), self.symbol(SymbolKind::Variable, format!("{} {}{}", keyword, name, annotation)))]
+                        }
+                    });
+                }
+                symbols
+            }
+            // `declare function ...` 등 (.d.ts)
+            ("ambient_declaration", _) => {
+                let mut cursor = node.walk();
+                let declarations: Vec<Node> = node.named_children(&mut cursor).collect();
+                declarations.into_iter().flat_map(|declaration| self.declaration_symbols(declaration)).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// 값으로 내보낸 심볼 (`prefix`는 시그니처 앞에 붙는 `const name =` 등)
+    fn value_symbols(&self, name: &str, value: Node, prefix: &str) -> Vec<(String, Symbol)> {
+        match value.kind() {
+            "class" => self.class_symbols(name, value, format!("{} ", prefix)),
+            "object" => {
+                let mut symbols = vec![(name.to_string(), self.symbol(SymbolKind::Variable, format!("{} {{…}}", prefix)))];
+                symbols.extend(self.object_members(Some(name), value));
+                symbols
+            }
+            _ if is_function_or_class(value) => {
+                vec![(name.to_string(), self.symbol(SymbolKind::Function, format!("{} {}", prefix, self.header(value))))]
+            }
+            _ => vec![(name.to_string(), self.symbol(SymbolKind::Variable, prefix.to_string()))],
+        }
+    }
+
+    /// 클래스와 공개 멤버 (`#private`, `private`, `protected` 멤버는 제외)
+    fn class_symbols(&self, name: &str, node: Node, prefix: String) -> Vec<(String, Symbol)> {
+        let mut symbols = vec![(name.to_string(), self.symbol(SymbolKind::Class, format!("{}{}", prefix, self.header(node))))];
+        let Some(body) = node.child_by_field_name("body") else {
+            return symbols;
+        };
+
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            let kind = match member.kind() {
+                "method_definition" | "method_signature" | "abstract_method_signature" => SymbolKind::Method,
+                "field_definition" | "public_field_definition" => SymbolKind::Property,
+                _ => continue,
+            };
+            let Some(member_name) = member.child_by_field_name("name").or_else(|| member.child_by_field_name("property")) else {
+                continue;
+            };
+            if member_name.kind() == "private_property_identifier" || self.is_hidden(member) {
+                continue;
+            }
+            let signature = match kind {
+                SymbolKind::Method => self.header(member),
+                _ => self.header_before(member, "value"),
+            };
+            push_symbol(&mut symbols, format!("{}.{}", name, self.text(member_name)), self.symbol(kind, signature));
+        }
+        symbols
+    }
+
+    /// 인터페이스/객체 타입 멤버
+    fn type_members(&self, name: &str, body: Node) -> Vec<(String, Symbol)> {
+        let mut symbols = Vec::new();
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            let (key, kind) = match member.kind() {
+                "property_signature" | "method_signature" => {
+                    let Some(member_name) = member.child_by_field_name("name") else {
+                        continue;
+                    };
+                    let kind = match member.kind() {
+                        "method_signature" => SymbolKind::Method,
+                        _ => SymbolKind::Property,
+                    };
+                    (format!("{}.{}", name, self.text(member_name)), kind)
+                }
+                "call_signature" => (format!("{}()", name), SymbolKind::Method),
+                "construct_signature" => (format!("new {}()", name), SymbolKind::Method),
+                "index_signature" => (format!("{}[]", name), SymbolKind::Property),
+                _ => continue,
+            };
+            let signature = self.text(member).trim_end_matches([';', ',']).to_string();
+            push_symbol(&mut symbols, key, self.symbol(kind, signature));
+        }
+        symbols
+    }
+
+    /// 객체 리터럴의 속성 (옵션 기본값 등이 바뀌면 사용자 동작이 달라지므로 값까지 비교)
+    fn object_members(&self, name: Option<&str>, object: Node) -> Vec<(String, Symbol)> {
+        let key_of = |member: &str| match name {
+            Some(name) => format!("{}.{}", name, member),
+            None => member.to_string(),
+        };
+        let mut symbols = Vec::new();
+        let mut cursor = object.walk();
+        for member in object.named_children(&mut cursor) {
+            let (member_name, symbol) = match member.kind() {
+                "pair" => {
+                    let (Some(key), Some(value)) = (member.child_by_field_name("key"), member.child_by_field_name("value")) else {
+                        continue;
+                    };
+                    let key = self.text(key).trim_matches(['\'', '"']).to_string();
+                    let symbol = match is_function_or_class(value) {
+                        true => self.symbol(SymbolKind::Function, format!("{}: {}", key, self.header(value))),
+                        false => self.symbol(SymbolKind::Property, self.text(member).to_string()),
+                    };
+                    (key, symbol)
+                }
+                // `{ attach }`는 같은 이름의 선언을 내보냄
+                "shorthand_property_identifier" => {
+                    let member_name = self.text(member);
+                    for (key, symbol) in self.named_export(member_name, &key_of(member_name)) {
+                        push_symbol(&mut symbols, key, symbol);
+                    }
+                    continue;
+                }
+                "method_definition" => {
+                    let Some(member_name) = member.child_by_field_name("name") else {
+                        continue;
+                    };
+                    (self.text(member_name).to_string(), self.symbol(SymbolKind::Method, self.header(member)))
+                }
+                _ => continue,
+            };
+            push_symbol(&mut symbols, key_of(&member_name), symbol);
+        }
+        symbols
+    }
+
+    /// TypeScript 접근 제한자로 감춘 멤버 여부
+    fn is_hidden(&self, member: Node) -> bool {
+        let mut cursor = member.walk();
+        let hidden = member
+            .children(&mut cursor)
+            .any(|child| child.kind() == "accessibility_modifier" && matches!(self.text(child), "private" | "protected"));
+        hidden
+    }
+
+    fn symbol(&self, kind: SymbolKind, signature: String) -> Symbol {
+        Symbol { kind, signature: truncate_signature(&normalize(&signature)) }
+    }
+
+    /// 본문을 뺀 선언부 (`function play(options) `, `class Player extends Base ` 등)
+    fn header(&self, node: Node) -> String {
+        self.header_before(node, "body")
+    }
+
+    fn header_before(&self, node: Node, field: &str) -> String {
+        let end = node.child_by_field_name(field).map_or(node.end_byte(), |child| child.start_byte());
+        self.source[node.start_byte()..end].trim_end().trim_end_matches(['=', ';']).to_string()
+    }
+
+    fn text(&self, node: Node) -> &'a str {
+        &self.source[node.byte_range()]
+    }
+}
+
+/// 함수/클래스 값 여부 (`const play = () => {}` 등)
+fn is_function_or_class(node: Node) -> bool {
+    matches!(
+        node.kind(),
+        "arrow_function" | "function_expression" | "function" | "generator_function" | "class"
+    )
+}
+
+/// 같은 이름의 멤버(getter/setter, 오버로드)는 시그니처를 이어 붙여 하나로 비교
+fn push_symbol(symbols: &mut Vec<(String, Symbol)>, key: String, symbol: Symbol) {
+    match symbols.iter_mut().find(|(existing, _)| *existing == key) {
+        Some((_, existing)) => existing.signature = format!("{} / {}", existing.signature, symbol.signature),
+        None => symbols.push((key, symbol)),
+    }
+}
+
+/// 기본 내보내기는 선언 이름과 관계없이 `default`로 비교
+fn default_export(symbols: Vec<(String, Symbol)>) -> Vec<(String, Symbol)> {
+    let Some(root) = symbols.first().map(|(name, _)| name.clone()) else {
+        return symbols;
+    };
+    symbols
+        .into_iter()
+        .filter_map(|(name, symbol)| Some((rename(&name, &root, "default")?, symbol)))
+        .collect()
+}
+
+/// `local`과 그 멤버(`local.x`)의 이름을 `exported` 기준으로 바꿈 (다른 심볼이면 None)
+fn rename(name: &str, local: &str, exported: &str) -> Option<String> {
+    match name.strip_prefix(local)? {
+        "" => Some(exported.to_string()),
+        member if member.starts_with('.') => Some(format!("{}{}", exported, member)),
+        _ => None,
+    }
+}
+
+/// 포매터 차이(줄바꿈, 괄호 안 공백, 끝 쉼표)를 무시하도록 공백 정리
+fn normalize(text: &str) -> String {
+    let mut normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    for (from, to) in [
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        ("{ ", "{"),
+        (" }", "}"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (",)", ")"),
+        (",]", "]"),
+        (",}", "}"),
+        (",>", ">"),
+    ] {
+        normalized = normalized.replace(from, to);
+    }
+    normalized
+}
+
+fn truncate_signature(signature: &str) -> String {
+    match signature.char_indices().nth(MAX_SIGNATURE_CHARS) {
+        Some((index, _)) => format!("{}…", &signature[..index]),
+        None => signature.to_string(),
+    }
+}
+
+fn code_span(text: &str) -> String {
+    format!("`{}`", text.replace('`', "'"))
+}
+
+/// 마크다운 표 안에서도 깨지지 않는 인라인 코드
+fn table_code_span(text: &str) -> String {
+    code_span(text).replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exports(path: &str, source: &str) -> Exports {
+        let language = language_for(Path::new(path)).unwrap();
+        parse_exports(source, &language).unwrap().unwrap()
+    }
+
+    fn keys(exports: &Exports) -> Vec<&str> {
+        exports.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn declarations_and_class_members() {
+        let exports = exports(
+            "player.ts",
+            "export function play(url: string): void { start(url); }\n\
+             export class Player {\n  play(): void {}\n  private secret() {}\n  #hidden() {}\n}\n\
+             export interface Options { autoplay?: boolean }\n\
+             function internal() {}\n",
+        );
+        assert_eq!(keys(&exports), ["Options", "Options.autoplay", "Player", "Player.play", "play"]);
+        assert_eq!(exports["play"].signature, "function play(url: string): void");
+        assert!(matches!(exports["Player"].kind, SymbolKind::Class));
+    }
+
+    #[test]
+    fn re_exports_and_default_export() {
+        let exports = exports(
+            "index.ts",
+            "export { load as open } from './loader';\nexport * from './types';\nexport default class Foo { bar() {} }\n",
+        );
+        assert_eq!(keys(&exports), ["* from './types'", "default", "default.bar", "open"]);
+        assert!(matches!(exports["open"].kind, SymbolKind::ReExport));
+        assert_eq!(exports["default"].signature, "class Foo");
+    }
+
+    #[test]
+    fn commonjs_exports() {
+        let exports = exports("util.js", "function a() {}\nmodule.exports = { a, b: 1 };\nexports.c = function(x) {};\n");
+        assert_eq!(keys(&exports), ["a", "b", "c"]);
+        assert_eq!(exports["a"].signature, "function a()");
+    }
+
+    #[test]
+    fn delta_of_added_removed_and_changed_symbols() {
+        let old = exports("api.js", "export function keep() {}\nexport function change(a) {}\nexport function drop() {}\n");
+        let new = exports("api.js", "export function keep() {}\nexport function change(a, b) {}\nexport const add = 1;\n");
+        let changes = diff_exports("api.js", old, new);
+        let summary: Vec<(ChangeKind, &str)> = changes.iter().map(|change| (change.change, change.name.as_str())).collect();
+        assert_eq!(summary, [(ChangeKind::Added, "add"), (ChangeKind::Changed, "change"), (ChangeKind::Removed, "drop")]);
+        assert_eq!(changes[1].signature(code_span), "`function change(a)` → `function change(a, b)`");
+    }
+}
@@ -0,0 +1,227 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::prompt::PROMPT_TEMPLATE_VERSION;
+use crate::provider::CompletionRequest;
+
+/// 사용자 캐시 디렉토리 아래에 만드는 디렉토리 이름
+const CACHE_DIR_NAME: &str = "git-diff-analyzer";
+
+/// 같은 프로세스의 동시 저장이 임시 파일을 공유하지 않도록 붙이는 순번
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// 캐시 파일 하나의 내용
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    provider: String,
+    model: String,
+    /// 저장 시각 (UNIX 초)
+    created_at: u64,
+    response: String,
+}
+
+/// 캐시 항목 수와 전체 크기
+#[derive(Default)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}개 ({:.1} MB)", self.entries, self.bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// 디스크에 저장하는 LLM 응답 캐시
+///
+/// 키는 제공자, 모델, 프롬프트 템플릿 버전, 생성 옵션(구조화 출력 스키마 포함), 프롬프트 원문(분석할 diff 내용 포함)의 SHA-256이므로
+/// 같은 범위를 같은 설정으로 다시 분석하면 API를 호출하지 않고 저장된 응답을 사용함.
+/// 항목은 `<키 앞 2자리>/<키>.json`에 저장됨
+pub struct ResponseCache {
+    dir: PathBuf,
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// 기본 캐시 디렉토리 (Linux는 `~/.cache/git-diff-analyzer`)
+    pub fn default_dir() -> Result<PathBuf> {
+        dirs::cache_dir()
+            .map(|dir| dir.join(CACHE_DIR_NAME))
+            .ok_or_else(|| anyhow!("사용자 캐시 디렉토리를 찾을 수 없습니다. --cache-dir로 지정해주세요."))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 요청의 캐시 키 (16진수 SHA-256)
+    pub fn key(provider: &str, request: &CompletionRequest) -> String {
+        let fields = [
+            provider.to_string(),
+            request.model.clone(),
+            PROMPT_TEMPLATE_VERSION.to_string(),
+            request.max_tokens.to_string(),
+            request.temperature.to_string(),
+            request.json_schema.as_ref().map(|schema| schema.to_string()).unwrap_or_default(),
+        ];
+
+        let mut hasher = Sha256::new();
+        for field in &fields {
+            hasher.update(field.as_bytes());
+            // 필드 경계가 섞여 다른 요청과 같은 키가 되지 않도록 구분
+            hasher.update([0]);
+        }
+        hasher.update(request.prompt.as_bytes());
+        hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(&key[..2]).join(format!("{}.json", key))
+    }
+
+    /// 저장된 응답 (없거나 손상된 항목이면 None)
+    pub fn get(&self, key: &str) -> Option<String> {
+        let content = fs::read_to_string(self.entry_path(key)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        Some(entry.response)
+    }
+
+    pub fn put(&self, key: &str, provider: &str, model: &str, response: &str) -> Result<()> {
+        let path = self.entry_path(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let entry = CacheEntry {
+            provider: provider.to_string(),
+            model: model.to_string(),
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            response: response.to_string(),
+        };
+
+        // 동시에 실행 중인 다른 분석이 쓰다 만 파일을 읽지 않도록 임시 파일에 쓴 뒤 이름 변경
+        let temp_path = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp_path, serde_json::to_string(&entry)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// 캐시 항목 파일과 메타데이터 목록
+    fn entries(&self) -> Result<Vec<(PathBuf, fs::Metadata)>> {
+        let mut entries = Vec::new();
+        if !self.dir.is_dir() {
+            return Ok(entries);
+        }
+
+        for shard in fs::read_dir(&self.dir)? {
+            let shard = shard?.path();
+            if !shard.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(&shard)? {
+                let path = file?.path();
+                if path.extension().is_some_and(|ext| ext == "json") {
+                    let metadata = fs::metadata(&path)?;
+                    entries.push((path, metadata));
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    pub fn stats(&self) -> Result<CacheStats> {
+        let mut stats = CacheStats::default();
+        for (_, metadata) in self.entries()? {
+            stats.entries += 1;
+            stats.bytes += metadata.len();
+        }
+        Ok(stats)
+    }
+
+    /// 저장한 지 `max_age`가 지난 항목을 삭제하고 삭제한 양을 반환
+    ///
+    /// 저장 시각은 항목의 `created_at`을 사용하고, 읽을 수 없는 항목은 파일 수정 시각을 사용함
+    pub fn prune(&self, max_age: Duration) -> Result<CacheStats> {
+        let now = SystemTime::now();
+        self.remove_entries(|path, metadata| {
+            let created_at = fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<CacheEntry>(&content).ok())
+                .map(|entry| UNIX_EPOCH + Duration::from_secs(entry.created_at))
+                .or_else(|| metadata.modified().ok());
+            created_at
+                .and_then(|created_at| now.duration_since(created_at).ok())
+                .is_some_and(|age| age > max_age)
+        })
+    }
+
+    /// 모든 항목을 삭제하고 삭제한 양을 반환
+    pub fn clear(&self) -> Result<CacheStats> {
+        self.remove_entries(|_, _| true)
+    }
+
+    /// 조건에 맞는 항목만 삭제 (캐시 디렉토리의 다른 파일은 건드리지 않음)
+    fn remove_entries(&self, should_remove: impl Fn(&Path, &fs::Metadata) -> bool) -> Result<CacheStats> {
+        let mut removed = CacheStats::default();
+        for (path, metadata) in self.entries()? {
+            if !should_remove(&path, &metadata) {
+                continue;
+            }
+            fs::remove_file(&path)?;
+            removed.entries += 1;
+            removed.bytes += metadata.len();
+
+            // 비어 있는 하위 디렉토리 정리 (다른 항목이 남아 있으면 실패하므로 무시)
+            if let Some(shard) = path.parent() {
+                fs::remove_dir(shard).ok();
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prune_uses_created_at() {
+        let dir = std::env::temp_dir().join(format!("git-diff-analyzer-cache-{}", std::process::id()));
+        let cache = ResponseCache::new(dir.clone());
+        let (old_key, new_key) = ("aa".repeat(32), "bb".repeat(32));
+        cache.put(&old_key, "OpenAI", "gpt-4o", "old").unwrap();
+        cache.put(&new_key, "OpenAI", "gpt-4o", "new").unwrap();
+
+        // 파일 수정 시각은 방금이지만 저장 시각은 이틀 전인 항목 (복사나 백업 복원 등)
+        let two_days_ago = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() - 2 * 24 * 60 * 60;
+        let entry = CacheEntry {
+            provider: "OpenAI".to_string(),
+            model: "gpt-4o".to_string(),
+            created_at: two_days_ago,
+            response: "old".to_string(),
+        };
+        fs::write(cache.entry_path(&old_key), serde_json::to_string(&entry).unwrap()).unwrap();
+
+        let removed = cache.prune(Duration::from_secs(24 * 60 * 60)).unwrap();
+        let (old, new) = (cache.get(&old_key), cache.get(&new_key));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(removed.entries, 1);
+        assert_eq!(old, None);
+        assert_eq!(new.as_deref(), Some("new"));
+    }
+}
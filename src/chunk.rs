@@ -0,0 +1,106 @@
+use crate::diff::{Diff, DiffLine, FileDiff, Hunk};
+use crate::tokenizer::TokenCounter;
+
+/// diff를 토큰 예산 이하의 청크들로 분할
+///
+/// 가능한 한 파일 단위로 묶고, 한 파일이 예산보다 크면 hunk 단위로,
+/// 한 hunk도 예산보다 크면 줄 단위로 나눔. 나뉜 조각에는 파일 헤더가 반복됨.
+pub fn split_diff(diff: &Diff, max_tokens: usize, tokens: TokenCounter) -> Vec<Diff> {
+    let mut chunks = Vec::new();
+    let mut current = Diff {
+        preamble: diff.preamble.clone(),
+        files: Vec::new(),
+    };
+    let mut current_tokens = tokens.count(&diff.preamble);
+
+    for file in &diff.files {
+        for piece in split_file(file, max_tokens, tokens) {
+            let piece_tokens = tokens.count(&piece.to_patch());
+            if current_tokens + piece_tokens > max_tokens && !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+                current_tokens = 0;
+            }
+            current.files.push(piece);
+            current_tokens += piece_tokens;
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn split_file(file: &FileDiff, max_tokens: usize, tokens: TokenCounter) -> Vec<FileDiff> {
+    if tokens.count(&file.to_patch()) <= max_tokens {
+        return vec![file.clone()];
+    }
+
+    let header_tokens = tokens.count(&file.header.join("\n"));
+    let hunk_budget = max_tokens.saturating_sub(header_tokens).max(1);
+
+    let mut pieces = Vec::new();
+    let mut hunks = Vec::new();
+    let mut hunks_tokens = 0;
+
+    for hunk in file.hunks.iter().flat_map(|hunk| split_hunk(hunk, hunk_budget, tokens)) {
+        let hunk_tokens = tokens.count(&hunk.to_patch());
+        if hunks_tokens + hunk_tokens > hunk_budget && !hunks.is_empty() {
+            pieces.push(file_with_hunks(file, std::mem::take(&mut hunks)));
+            hunks_tokens = 0;
+        }
+        hunks.push(hunk);
+        hunks_tokens += hunk_tokens;
+    }
+
+    if !hunks.is_empty() || pieces.is_empty() {
+        pieces.push(file_with_hunks(file, hunks));
+    }
+    pieces
+}
+
+fn split_hunk(hunk: &Hunk, max_tokens: usize, tokens: TokenCounter) -> Vec<Hunk> {
+    if tokens.count(&hunk.to_patch()) <= max_tokens {
+        return vec![hunk.clone()];
+    }
+
+    let budget = max_tokens.saturating_sub(tokens.count(&hunk.header)).max(1);
+    let mut pieces = Vec::new();
+    let mut lines = Vec::new();
+    let mut lines_tokens = 0;
+
+    for line in &hunk.lines {
+        let line_tokens = tokens.count(&line.content) + 1;
+        if lines_tokens + line_tokens > budget && !lines.is_empty() {
+            pieces.push(hunk_with_lines(hunk, std::mem::take(&mut lines)));
+            lines_tokens = 0;
+        }
+        lines.push(line.clone());
+        lines_tokens += line_tokens;
+    }
+
+    if !lines.is_empty() {
+        pieces.push(hunk_with_lines(hunk, lines));
+    }
+    pieces
+}
+
+fn hunk_with_lines(hunk: &Hunk, lines: Vec<DiffLine>) -> Hunk {
+    Hunk {
+        header: hunk.header.clone(),
+        old_lines: hunk.old_lines,
+        new_lines: hunk.new_lines,
+        lines,
+    }
+}
+
+fn file_with_hunks(file: &FileDiff, hunks: Vec<Hunk>) -> FileDiff {
+    FileDiff {
+        old_path: file.old_path.clone(),
+        new_path: file.new_path.clone(),
+        status: file.status,
+        binary: file.binary,
+        header: file.header.clone(),
+        hunks,
+    }
+}
@@ -0,0 +1,403 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::Path;
+
+use crate::diff::{Diff, LineKind};
+
+/// 호환성 항목 종류
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CompatKind {
+    /// 런타임 API (폴리필이 없으면 그대로 실패)
+    Api,
+    /// 언어 문법 (트랜스파일하면 문제가 되지 않을 수 있음)
+    Syntax,
+}
+
+/// 호환성 데이터 표의 한 항목
+struct CompatEntry {
+    name: &'static str,
+    kind: CompatKind,
+    /// 추가된 줄에서 사용 여부를 판단하는 정규식
+    pattern: &'static str,
+    /// 지원을 시작한 Chrome 버전
+    chrome: u32,
+    note: &'static str,
+}
+
+/// Chrome 지원 버전 표 (MDN browser-compat-data 기준)
+const COMPAT_DATA: &[CompatEntry] = &[
+    // 런타임 API
+    CompatEntry { name: "Promise", kind: CompatKind::Api, pattern: r"\bPromise\b", chrome: 32, note: "" },
+    CompatEntry { name: "Map/Set", kind: CompatKind::Api, pattern: r"\bnew\s+(Map|Set)\b", chrome: 38, note: "" },
+    CompatEntry { name: "Symbol", kind: CompatKind::Api, pattern: r"\bSymbol\b", chrome: 38, note: "" },
+    CompatEntry { name: "String.prototype.startsWith/endsWith", kind: CompatKind::Api, pattern: r"\.(startsWith|endsWith)\s*\(", chrome: 41, note: "" },
+    CompatEntry { name: "String.prototype.repeat", kind: CompatKind::Api, pattern: r"\.repeat\s*\(", chrome: 41, note: "" },
+    CompatEntry { name: "Element.closest", kind: CompatKind::Api, pattern: r"\.closest\s*\(", chrome: 41, note: "jQuery 등 동명 메서드일 수 있음" },
+    CompatEntry { name: "fetch()", kind: CompatKind::Api, pattern: r"(^|[^\w$.])fetch\s*\(|\bwindow\.fetch\s*\(", chrome: 42, note: "" },
+    CompatEntry { name: "Object.assign()", kind: CompatKind::Api, pattern: r"\bObject\.assign\s*\(", chrome: 45, note: "" },
+    CompatEntry { name: "Array.prototype.find/findIndex", kind: CompatKind::Api, pattern: r"\.(find|findIndex)\s*\(", chrome: 45, note: "jQuery 등 동명 메서드일 수 있음" },
+    CompatEntry { name: "Array.from/Array.of", kind: CompatKind::Api, pattern: r"\bArray\.(from|of)\s*\(", chrome: 45, note: "" },
+    CompatEntry { name: "Array.prototype.includes", kind: CompatKind::Api, pattern: r"\.includes\s*\(", chrome: 47, note: "String.prototype.includes라면 Chrome 41+" },
+    CompatEntry { name: "requestIdleCallback", kind: CompatKind::Api, pattern: r"\brequestIdleCallback\s*\(", chrome: 47, note: "" },
+    CompatEntry { name: "navigator.mediaDevices", kind: CompatKind::Api, pattern: r"\bnavigator\.mediaDevices\b", chrome: 47, note: "" },
+    CompatEntry { name: "URLSearchParams", kind: CompatKind::Api, pattern: r"\bURLSearchParams\b", chrome: 49, note: "" },
+    CompatEntry { name: "Proxy/Reflect", kind: CompatKind::Api, pattern: r"\bnew\s+Proxy\s*\(|\bReflect\.\w+", chrome: 49, note: "" },
+    CompatEntry { name: "HTMLMediaElement.play() Promise", kind: CompatKind::Api, pattern: r"\.play\s*\(\s*\)\s*\.\s*(then|catch|finally)\b", chrome: 50, note: "이전 버전에서는 undefined를 반환하므로 TypeError 발생" },
+    CompatEntry { name: "IntersectionObserver", kind: CompatKind::Api, pattern: r"\bIntersectionObserver\b", chrome: 51, note: "" },
+    CompatEntry { name: "Object.values/entries", kind: CompatKind::Api, pattern: r"\bObject\.(values|entries)\s*\(", chrome: 54, note: "" },
+    CompatEntry { name: "customElements", kind: CompatKind::Api, pattern: r"\bcustomElements\.", chrome: 54, note: "" },
+    CompatEntry { name: "PointerEvent", kind: CompatKind::Api, pattern: r"\bPointerEvent\b", chrome: 55, note: "" },
+    CompatEntry { name: "String.prototype.padStart/padEnd", kind: CompatKind::Api, pattern: r"\.(padStart|padEnd)\s*\(", chrome: 57, note: "" },
+    CompatEntry { name: "Promise.prototype.finally", kind: CompatKind::Api, pattern: r"\.finally\s*\(", chrome: 63, note: "" },
+    CompatEntry { name: "ResizeObserver", kind: CompatKind::Api, pattern: r"\bResizeObserver\b", chrome: 64, note: "" },
+    CompatEntry { name: "AbortController", kind: CompatKind::Api, pattern: r"\bAbortController\b", chrome: 66, note: "" },
+    CompatEntry { name: "Array.prototype.flat/flatMap", kind: CompatKind::Api, pattern: r"\.(flat|flatMap)\s*\(", chrome: 69, note: "" },
+    CompatEntry { name: "Object.fromEntries", kind: CompatKind::Api, pattern: r"\bObject\.fromEntries\s*\(", chrome: 73, note: "" },
+    CompatEntry { name: "Promise.allSettled", kind: CompatKind::Api, pattern: r"\bPromise\.allSettled\s*\(", chrome: 76, note: "" },
+    // 언어 문법
+    CompatEntry { name: "for...of", kind: CompatKind::Syntax, pattern: r"\bfor\s*\([^)]*\bof\b", chrome: 38, note: "" },
+    CompatEntry { name: "템플릿 리터럴", kind: CompatKind::Syntax, pattern: r"`", chrome: 41, note: "" },
+    CompatEntry { name: "화살표 함수", kind: CompatKind::Syntax, pattern: r"=>", chrome: 45, note: "" },
+    CompatEntry { name: "전개/나머지 구문 (...)", kind: CompatKind::Syntax, pattern: r"\.\.\.[\w$\[{(]", chrome: 46, note: "객체 전개는 Chrome 60+" },
+    CompatEntry { name: "let/const", kind: CompatKind::Syntax, pattern: r"\b(let|const)\s+[\w$\[{]", chrome: 49, note: "strict mode에서는 Chrome 41+" },
+    CompatEntry { name: "class", kind: CompatKind::Syntax, pattern: r"\bclass\s+[A-Za-z_$]|\bclass\s*\{", chrome: 49, note: "" },
+    CompatEntry { name: "구조 분해 할당", kind: CompatKind::Syntax, pattern: r"\b(let|const|var)\s*[\[{]", chrome: 49, note: "" },
+    CompatEntry { name: "거듭제곱 연산자 (**)", kind: CompatKind::Syntax, pattern: r"\*\*", chrome: 52, note: "" },
+    CompatEntry { name: "async/await", kind: CompatKind::Syntax, pattern: r"\basync\s+(function\b|\(|[A-Za-z_$][\w$]*\s*=>)|\bawait\s", chrome: 55, note: "" },
+    CompatEntry { name: "ES 모듈 (import/export)", kind: CompatKind::Syntax, pattern: r"^\s*(import|export)\b", chrome: 61, note: "" },
+    CompatEntry { name: "옵셔널 체이닝 (?.)", kind: CompatKind::Syntax, pattern: r"\?\.[A-Za-z_$\[(]", chrome: 80, note: "" },
+    CompatEntry { name: "Null 병합 연산자 (??)", kind: CompatKind::Syntax, pattern: r"\?\?", chrome: 80, note: "" },
+];
+
+/// 보고서에 표시할 코드의 최대 글자 수
+const MAX_CODE_CHARS: usize = 120;
+
+/// 항목별로 보고서와 프롬프트에 나열할 최대 위치 수 (나머지는 개수만 표시)
+const MAX_LISTED_LOCATIONS: usize = 5;
+
+/// 검사 대상 JS/TS 파일 확장자
+const SCRIPT_EXTENSIONS: &[&str] = &["js", "mjs", "cjs", "jsx", "ts", "mts", "cts", "tsx"];
+/// TypeScript 파일 확장자 (컴파일러가 대상 버전에 맞게 문법을 변환하므로 문법 항목은 검사하지 않음)
+const TYPESCRIPT_EXTENSIONS: &[&str] = &["ts", "mts", "cts", "tsx"];
+
+/// 기준 버전보다 새로운 API 사용 한 건
+pub struct CompatFinding {
+    pub path: String,
+    pub line: Option<u32>,
+    pub name: &'static str,
+    pub chrome: u32,
+    pub code: String,
+    note: &'static str,
+    kind: CompatKind,
+}
+
+/// 정적 호환성 검사 결과
+pub struct CompatReport {
+    pub baseline: u32,
+    pub findings: Vec<CompatFinding>,
+}
+
+/// 같은 항목의 발견 목록 (처음 발견된 순서)
+pub struct CompatGroup<'a> {
+    pub findings: Vec<&'a CompatFinding>,
+}
+
+/// 추가된 JS/TS 줄에서 Chrome 기준 버전보다 새로운 API를 찾는 정적 검사기
+pub struct CompatScanner {
+    baseline: u32,
+    rules: Vec<(&'static CompatEntry, Regex)>,
+}
+
+impl CompatScanner {
+    /// 기준 버전보다 나중에 지원된 항목만 검사
+    pub fn new(baseline: u32) -> Result<Self> {
+        let rules = COMPAT_DATA
+            .iter()
+            .filter(|entry| entry.chrome > baseline)
+            .map(|entry| {
+                Regex::new(entry.pattern)
+                    .with_context(|| format!("호환성 데이터의 정규식이 잘못되었습니다: {}", entry.name))
+                    .map(|regex| (entry, regex))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { baseline, rules })
+    }
+
+    pub fn scan(&self, diff: &Diff) -> CompatReport {
+        let mut findings = Vec::new();
+
+        for file in diff.files.iter().filter(|file| is_script(file.path())) {
+            let path = file.path().display().to_string();
+            let typescript = has_extension(file.path(), TYPESCRIPT_EXTENSIONS);
+            let rules: Vec<_> = self
+                .rules
+                .iter()
+                .filter(|(entry, _)| !(typescript && entry.kind == CompatKind::Syntax))
+                .collect();
+            for line in file.hunks.iter().flat_map(|hunk| &hunk.lines) {
+                if line.kind != LineKind::Added {
+                    continue;
+                }
+                let code = strip_comments_and_strings(&line.content);
+                for (entry, regex) in &rules {
+                    if regex.is_match(&code) {
+                        findings.push(CompatFinding {
+                            path: path.clone(),
+                            line: line.new_lineno,
+                            name: entry.name,
+                            chrome: entry.chrome,
+                            code: truncate_code(line.content.trim()),
+                            note: entry.note,
+                            kind: entry.kind,
+                        });
+                    }
+                }
+            }
+        }
+
+        CompatReport { baseline: self.baseline, findings }
+    }
+}
+
+impl CompatFinding {
+    fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.path, line),
+            None => self.path.clone(),
+        }
+    }
+
+    fn description(&self) -> String {
+        let mut description = format!("{} (Chrome {}+)", self.name, self.chrome);
+        if self.kind == CompatKind::Syntax {
+            description.push_str(", 문법");
+        }
+        if !self.note.is_empty() {
+            description.push_str(&format!(", {}", self.note));
+        }
+        description
+    }
+}
+
+impl CompatGroup<'_> {
+    /// 항목 설명 (첫 발견 기준, 같은 항목이면 모두 같음)
+    pub fn description(&self) -> String {
+        self.findings[0].description()
+    }
+
+    /// 나열할 발견 (최대 MAX_LISTED_LOCATIONS개)
+    pub fn listed(&self) -> &[&CompatFinding] {
+        &self.findings[..self.findings.len().min(MAX_LISTED_LOCATIONS)]
+    }
+
+    /// 나열하지 않은 발견 수
+    pub fn omitted(&self) -> usize {
+        self.findings.len() - self.listed().len()
+    }
+}
+
+impl CompatReport {
+    /// 항목별로 묶은 발견 목록 (항목이 처음 발견된 순서)
+    pub fn groups(&self) -> Vec<CompatGroup<'_>> {
+        let mut groups: Vec<CompatGroup> = Vec::new();
+        for finding in &self.findings {
+            match groups.iter_mut().find(|group| group.findings[0].name == finding.name) {
+                Some(group) => group.findings.push(finding),
+                None => groups.push(CompatGroup { findings: vec![finding] }),
+            }
+        }
+        groups
+    }
+
+    /// 프롬프트에 사실로 포함할 검사 결과
+    pub fn prompt_section(&self) -> String {
+        let mut section = format!(
+            "**정적 호환성 검사 결과 (Chrome {} 기준, 추가된 JS/TS 줄 대상):**\n",
+            self.baseline
+        );
+        if self.findings.is_empty() {
+            section.push_str("호환성 데이터 표 기준으로 발견된 항목이 없습니다. 표에 없는 API는 직접 확인하세요.\n");
+            return section;
+        }
+
+        section.push_str("아래 항목은 호환성 데이터 표로 확인된 사실입니다. 추측하지 말고 보고서의 호환성 경고에 빠짐없이 반영하세요. 폴리필, 기능 감지, 트랜스파일(문법 항목) 등으로 실제 문제가 되지 않는 경우에는 그 근거를 명시하세요.\n");
+        for group in self.groups() {
+            section.push_str(&format!("- {}: {}건\n", group.description(), group.findings.len()));
+            for finding in group.listed() {
+                section.push_str(&format!("  - `{}`: `{}`\n", finding.location(), finding.code));
+            }
+            if group.omitted() > 0 {
+                section.push_str(&format!("  - 외 {}건\n", group.omitted()));
+            }
+        }
+        section
+    }
+
+    /// 분석 보고서 끝에 붙이는 마크다운 섹션
+    pub fn markdown(&self) -> String {
+        let mut report = format!("## 🔍 정적 호환성 검사 결과 (Chrome {} 기준)\n\n", self.baseline);
+        if self.findings.is_empty() {
+            report.push_str("추가된 JS/TS 코드에서 기준 버전보다 새로운 API가 발견되지 않았습니다.\n");
+            return report;
+        }
+
+        report.push_str("| 항목 | 건수 | 위치 | 코드 |\n|---|---|---|---|\n");
+        for group in self.groups() {
+            for (index, finding) in group.listed().iter().enumerate() {
+                let (description, count) = match index {
+                    0 => (group.description(), group.findings.len().to_string()),
+                    _ => (String::new(), String::new()),
+                };
+                report.push_str(&format!(
+                    "| {} | {} | `{}` | `{}` |\n",
+                    description,
+                    count,
+                    finding.location(),
+                    finding.code.replace('|', "\\|")
+                ));
+            }
+            if group.omitted() > 0 {
+                report.push_str(&format!("| | | 외 {}건 | |\n", group.omitted()));
+            }
+        }
+        report
+    }
+}
+
+fn truncate_code(code: &str) -> String {
+    match code.char_indices().nth(MAX_CODE_CHARS) {
+        Some((index, _)) => format!("{}…", &code[..index]),
+        None => code.to_string(),
+    }
+}
+
+fn is_script(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    // 타입 선언 파일은 실행되지 않음
+    if name.ends_with(".d.ts") {
+        return false;
+    }
+    has_extension(path, SCRIPT_EXTENSIONS)
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extensions.contains(&extension))
+}
+
+/// 주석과 문자열 내용을 제거해 코드 부분만 남김 (따옴표는 유지)
+///
+/// 줄 단위로만 처리하므로 여러 줄 주석의 중간 줄(`*`로 시작)은 통째로 건너뜀
+fn strip_comments_and_strings(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('*') || trimmed.starts_with("//") {
+        return String::new();
+    }
+
+    let mut code = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    chars.next();
+                } else if c == q {
+                    code.push(c);
+                    quote = None;
+                }
+            }
+            None => match (c, chars.peek()) {
+                ('/', Some('/')) => break,
+                ('/', Some('*')) => {
+                    // 같은 줄에서 닫히는 주석만 건너뜀
+                    chars.next();
+                    let mut previous = ' ';
+                    for c in chars.by_ref() {
+                        if previous == '*' && c == '/' {
+                            break;
+                        }
+                        previous = c;
+                    }
+                }
+                ('\'' | '"' | '`', _) => {
+                    code.push(c);
+                    quote = Some(c);
+                }
+                _ => code.push(c),
+            },
+        }
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn added(path: &str, lines: &[&str]) -> String {
+        let mut diff = format!(
+            "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -0,0 +1,{} @@\n",
+            lines.len()
+        );
+        for line in lines {
+            diff.push_str(&format!("+{}\n", line));
+        }
+        diff
+    }
+
+    fn scan(baseline: u32, diff: &str) -> Vec<(u32, &'static str)> {
+        let report = CompatScanner::new(baseline).unwrap().scan(&Diff::parse(diff.as_bytes()));
+        report.findings.iter().map(|finding| (finding.line.unwrap(), finding.name)).collect()
+    }
+
+    #[test]
+    fn strips_comments_and_strings() {
+        assert_eq!(strip_comments_and_strings("a.includes(b); // x.flat()"), "a.includes(b); ");
+        assert_eq!(strip_comments_and_strings("f(/* x ?? y */ z)"), "f( z)");
+        assert_eq!(strip_comments_and_strings(r#"log("a ?? \"b\"", 'c?.d')"#), r#"log("", '')"#);
+        assert_eq!(strip_comments_and_strings(" * Promise.allSettled()"), "");
+
+        let diff = added("src/app.js", &["// items.includes(x)", "log('a ?? b');", "x ?? y"]);
+        assert_eq!(scan(70, &diff), vec![(3, "Null 병합 연산자 (??)")]);
+    }
+
+    #[test]
+    fn skips_declaration_files() {
+        assert!(!is_script(Path::new("types/index.d.ts")));
+        assert!(is_script(Path::new("src/index.ts")));
+        assert!(!is_script(Path::new("README.md")));
+        assert!(scan(30, &added("types/index.d.ts", &["fetch(url)"])).is_empty());
+    }
+
+    #[test]
+    fn filters_by_baseline() {
+        let diff = added("src/app.js", &["items.flat()", "Object.assign(a, b)"]);
+        assert_eq!(scan(60, &diff), vec![(1, "Array.prototype.flat/flatMap")]);
+        assert!(scan(69, &diff).is_empty());
+    }
+
+    #[test]
+    fn skips_syntax_rules_for_typescript() {
+        let lines = ["const name = user?.name ?? fallback;", "items.flat()"];
+        assert_eq!(scan(60, &added("src/app.ts", &lines)), vec![(2, "Array.prototype.flat/flatMap")]);
+        assert!(scan(60, &added("src/app.js", &lines)).len() > 1);
+    }
+
+    #[test]
+    fn groups_and_caps_findings() {
+        let lines: Vec<String> = (0..8).map(|index| format!("items{}.flat()", index)).collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let report = CompatScanner::new(60).unwrap().scan(&Diff::parse(added("src/app.js", &lines).as_bytes()));
+
+        let groups = report.groups();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].findings.len(), 8);
+        assert_eq!(groups[0].listed().len(), MAX_LISTED_LOCATIONS);
+        assert_eq!(groups[0].omitted(), 3);
+        assert!(report.prompt_section().contains("외 3건"));
+        assert_eq!(report.markdown().matches("src/app.js:").count(), MAX_LISTED_LOCATIONS);
+    }
+}
@@ -0,0 +1,255 @@
+use anyhow::{Context, Result};
+use log::{info, warn};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::provider::ProviderKind;
+use crate::tags::DEFAULT_TAG_PREFIXES;
+
+/// 저장소별 설정 파일 이름
+pub const CONFIG_FILE_NAME: &str = ".diffanalyzer.toml";
+/// 사용자 설정 디렉토리 아래의 설정 파일 경로
+const USER_CONFIG_FILE: &str = "git-diff-analyzer/config.toml";
+
+/// 저장소 설정에서는 무시하는 키 (요청 대상 서버와 API 키 선택이 바뀌므로 사용자 설정이나 CLI 인자로만 지정)
+const USER_ONLY_KEYS: &[&str] = &["provider", "base_url"];
+
+/// 보고서 디렉토리 기본값
+pub const DEFAULT_OUTPUT_DIR: &str = "reports";
+
+/// 모델 컨텍스트 크기 기본값
+pub const DEFAULT_CONTEXT_WINDOW: usize = 128_000;
+/// 응답용으로 남겨두는 토큰 수 기본값
+pub const DEFAULT_MAX_OUTPUT_TOKENS: u32 = 4000;
+
+/// 설정 파일 내용 (사용자 설정과 `.diffanalyzer.toml`을 합친 결과)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileConfig {
+    /// LLM 제공자 (사용자 설정에서만 적용)
+    pub provider: Option<ProviderKind>,
+    /// 제공자 API 기본 URL (사용자 설정에서만 적용)
+    pub base_url: Option<String>,
+    /// 보고서를 저장할 디렉토리 (기본값: reports)
+    pub output_dir: Option<PathBuf>,
+    /// 기본 분석 프로필 이름 또는 프로필 파일 경로
+    pub profile: Option<String>,
+    /// 프로필 템플릿 변수 값 (프로필의 기본값을 덮어씀)
+    pub variables: BTreeMap<String, String>,
+    pub filter: FilterConfig,
+    pub compat: CompatConfig,
+    pub relevance: RelevanceConfig,
+    pub tags: TagsConfig,
+    /// 모델 체인 (`[[models]]`, 앞의 모델이 실패하면 다음 모델 사용)
+    pub models: Vec<ModelConfig>,
+}
+
+/// `[filter]` 섹션: 분석에서 제외하거나 포함할 파일 glob 패턴
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilterConfig {
+    /// 기본 제외 목록(lock 파일, 빌드 결과물 등) 사용 여부
+    pub default_excludes: bool,
+    /// 추가로 제외할 패턴
+    pub exclude: Vec<String>,
+    /// 지정하면 이 패턴에 해당하는 파일만 분석
+    pub include: Vec<String>,
+}
+
+impl Default for FilterConfig {
+    fn default() -> Self {
+        Self {
+            default_excludes: true,
+            exclude: Vec::new(),
+            include: Vec::new(),
+        }
+    }
+}
+
+/// `[compat]` 섹션: 정적 브라우저 호환성 검사 설정 (프로필에도 같은 섹션을 둘 수 있음)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CompatConfig {
+    /// 지원해야 하는 최소 Chrome 버전 (지정하지 않으면 검사하지 않음)
+    pub chrome: Option<u32>,
+}
+
+/// `[relevance]` 섹션: diff가 예산보다 클 때 먼저 분석할 파일을 고르는 기준 (프로필에도 같은 섹션을 둘 수 있음)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RelevanceConfig {
+    /// 바뀐 줄이나 경로에 나오면 관련도를 높이는 키워드 (예: `["MediaSource", "HTMLMediaElement"]`)
+    pub keywords: Vec<String>,
+}
+
+/// `[tags]` 섹션: 릴리스 태그를 semver로 해석하는 규칙
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TagsConfig {
+    /// 버전 앞에 붙는 태그 접두사 (예: `["v", "player-v"]`, 빈 문자열은 접두사 없는 태그)
+    pub prefixes: Vec<String>,
+}
+
+impl Default for TagsConfig {
+    fn default() -> Self {
+        Self {
+            prefixes: DEFAULT_TAG_PREFIXES.map(str::to_string).to_vec(),
+        }
+    }
+}
+
+/// `[[models]]` 항목: 모델 체인의 모델 하나와 토큰 한도
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelConfig {
+    pub name: String,
+    /// 모델 컨텍스트 크기 (프롬프트 + 응답)
+    #[serde(default = "default_context_window")]
+    pub context_window: usize,
+    /// 최대 응답 토큰 수
+    #[serde(default = "default_max_output_tokens")]
+    pub max_output_tokens: u32,
+    /// 프롬프트에 넣을 diff 내용의 최대 토큰 수 (기본값: 컨텍스트에 들어가는 최대값)
+    pub budget: Option<usize>,
+}
+
+impl ModelConfig {
+    /// 기본 한도를 사용하는 모델
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            context_window: DEFAULT_CONTEXT_WINDOW,
+            max_output_tokens: DEFAULT_MAX_OUTPUT_TOKENS,
+            budget: None,
+        }
+    }
+}
+
+fn default_context_window() -> usize {
+    DEFAULT_CONTEXT_WINDOW
+}
+
+fn default_max_output_tokens() -> u32 {
+    DEFAULT_MAX_OUTPUT_TOKENS
+}
+
+/// 사용자 설정 파일 경로 (Linux는 `~/.config/git-diff-analyzer/config.toml`)
+pub fn user_config_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(USER_CONFIG_FILE))
+}
+
+/// 저장소 설정 파일 경로 결정: 명시한 경로, 프로젝트 루트, 현재 디렉토리 순
+pub fn find_config_file(explicit: Option<&Path>, project_path: &Path) -> Option<PathBuf> {
+    if let Some(path) = explicit {
+        return Some(path.to_path_buf());
+    }
+
+    [project_path.join(CONFIG_FILE_NAME), PathBuf::from(CONFIG_FILE_NAME)]
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+/// 적용 순서대로의 설정 파일 (사용자 설정, 저장소 설정). 없는 사용자 설정 파일은 제외
+pub fn config_layers(explicit: Option<&Path>, project_path: &Path) -> Vec<PathBuf> {
+    user_config_file()
+        .filter(|path| path.is_file())
+        .into_iter()
+        .chain(find_config_file(explicit, project_path))
+        .collect()
+}
+
+/// 설정 파일들을 순서대로 합쳐서 로드 (뒤의 파일이 앞의 파일을 덮어씀)
+pub fn load_config(layers: &[PathBuf]) -> Result<FileConfig> {
+    let config = parse_merged(merge_layers(layers)?)?;
+
+    for path in layers {
+        info!("설정 파일: {}", path.display());
+    }
+    Ok(config)
+}
+
+/// 설정 파일들을 합친 TOML 테이블
+///
+/// 테이블(`[filter]` 등)은 키별로 합치고, 값과 배열(`[[models]]`, `exclude` 등)은 뒤의 파일 값으로 대체함.
+/// 사용자 설정이 아닌 파일(저장소 설정)의 `provider`, `base_url`은 무시함
+pub fn merge_layers(layers: &[PathBuf]) -> Result<toml::Table> {
+    let user_config = user_config_file();
+    let mut merged = toml::Table::new();
+    for path in layers {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("설정 파일을 읽을 수 없습니다: {}", path.display()))?;
+        // 파일별로 검증해서 오류 위치를 정확히 알려줌
+        toml::from_str::<FileConfig>(&content)
+            .with_context(|| format!("설정 파일 형식이 올바르지 않습니다: {}", path.display()))?;
+        let mut table = toml::from_str(&content)?;
+        if user_config.as_deref() != Some(path.as_path()) {
+            for key in remove_user_only_keys(&mut table) {
+                warn!(
+                    "경고: 저장소 설정 {}의 {} 값은 무시합니다. API 키가 다른 서버로 전송되지 않도록 사용자 설정이나 --{} 인자로 지정하세요.",
+                    path.display(),
+                    key,
+                    key.replace('_', "-")
+                );
+            }
+        }
+        merge_table(&mut merged, table);
+    }
+    // 합친 결과도 올바른 설정인지 확인
+    parse_merged(merged.clone())?;
+    Ok(merged)
+}
+
+fn parse_merged(merged: toml::Table) -> Result<FileConfig> {
+    toml::Value::Table(merged)
+        .try_into()
+        .context("설정 파일을 합친 결과가 올바르지 않습니다")
+}
+
+/// 저장소 설정에서 사용자 전용 키를 제거하고, 제거한 키 목록 반환
+fn remove_user_only_keys(table: &mut toml::Table) -> Vec<&'static str> {
+    USER_ONLY_KEYS.iter().copied().filter(|key| table.remove(*key).is_some()).collect()
+}
+
+fn merge_table(base: &mut toml::Table, over: toml::Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(over)) => merge_table(base, over),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_layer_cannot_set_endpoint() {
+        let dir = std::env::temp_dir().join(format!("git-diff-analyzer-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE_NAME);
+        fs::write(
+            &path,
+            "provider = \"openai-compatible\"\nbase_url = \"https://attacker.example\"\noutput_dir = \"out\"\n",
+        )
+        .unwrap();
+
+        let config = load_config(std::slice::from_ref(&path)).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(config.provider.is_none());
+        assert!(config.base_url.is_none());
+        assert_eq!(config.output_dir, Some(PathBuf::from("out")));
+    }
+
+    #[test]
+    fn removes_only_user_only_keys() {
+        let mut table: toml::Table = toml::from_str("base_url = \"http://x\"\nprofile = \"p\"").unwrap();
+        assert_eq!(remove_user_only_keys(&mut table), vec!["base_url"]);
+        assert_eq!(table.keys().collect::<Vec<_>>(), vec!["profile"]);
+    }
+}
@@ -0,0 +1,581 @@
+use std::path::{Path, PathBuf};
+
+/// 파일 단위 변경 종류
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileStatus {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+    Copied,
+}
+
+impl FileStatus {
+    /// `git diff --name-status`와 같은 한 글자 코드
+    pub fn code(self) -> char {
+        match self {
+            FileStatus::Added => 'A',
+            FileStatus::Deleted => 'D',
+            FileStatus::Modified => 'M',
+            FileStatus::Renamed => 'R',
+            FileStatus::Copied => 'C',
+        }
+    }
+}
+
+/// hunk 안의 한 줄 종류
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineKind {
+    Context,
+    Added,
+    Removed,
+    /// `\ No newline at end of file`
+    NoNewline,
+}
+
+impl LineKind {
+    fn prefix(self) -> char {
+        match self {
+            LineKind::Context => ' ',
+            LineKind::Added => '+',
+            LineKind::Removed => '-',
+            LineKind::NoNewline => '\\',
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DiffLine {
+    pub kind: LineKind,
+    pub content: String,
+    /// 새 파일 기준 줄 번호 (추가/컨텍스트 줄만)
+    pub new_lineno: Option<u32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Hunk {
+    /// 원본 `@@ -a,b +c,d @@ ...` 헤더 줄
+    pub header: String,
+    pub old_lines: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+impl Hunk {
+    pub fn to_patch(&self) -> String {
+        let mut out = String::new();
+        self.render(&mut out);
+        out
+    }
+
+    pub fn render(&self, out: &mut String) {
+        out.push_str(&self.header);
+        out.push('\n');
+        for line in &self.lines {
+            out.push(line.kind.prefix());
+            out.push_str(&line.content);
+            out.push('\n');
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FileDiff {
+    /// 이전 경로 (새로 추가된 파일은 None)
+    pub old_path: Option<PathBuf>,
+    /// 새 경로 (삭제된 파일은 None)
+    pub new_path: Option<PathBuf>,
+    pub status: FileStatus,
+    pub binary: bool,
+    /// `diff --git`부터 첫 hunk 전까지의 원본 헤더 줄들
+    pub header: Vec<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FileDiff {
+    /// 대표 경로 (새 경로, 삭제된 파일이면 이전 경로)
+    pub fn path(&self) -> &Path {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_else(|| Path::new(""))
+    }
+
+    /// 사람이 읽을 수 있는 경로 표시 (이름 변경 시 `old → new`)
+    pub fn display_path(&self) -> String {
+        match (&self.old_path, &self.new_path) {
+            (Some(old), Some(new)) if old != new => format!("{} → {}", old.display(), new.display()),
+            _ => self.path().display().to_string(),
+        }
+    }
+
+    pub fn added_lines(&self) -> usize {
+        self.count_lines(LineKind::Added)
+    }
+
+    /// 이름 변경/복사의 유사도 (`similarity index 87%` 헤더, 없으면 None)
+    pub fn similarity(&self) -> Option<u32> {
+        self.header
+            .iter()
+            .find_map(|line| line.strip_prefix("similarity index ")?.strip_suffix('%')?.parse().ok())
+    }
+
+    pub fn removed_lines(&self) -> usize {
+        self.count_lines(LineKind::Removed)
+    }
+
+    fn count_lines(&self, kind: LineKind) -> usize {
+        self.hunks
+            .iter()
+            .map(|hunk| hunk.lines.iter().filter(|line| line.kind == kind).count())
+            .sum()
+    }
+
+    pub fn to_patch(&self) -> String {
+        let mut out = String::new();
+        self.render(&mut out);
+        out
+    }
+
+    pub fn render(&self, out: &mut String) {
+        for line in &self.header {
+            out.push_str(line);
+            out.push('\n');
+        }
+        for hunk in &self.hunks {
+            hunk.render(out);
+        }
+    }
+}
+
+/// 파싱된 diff 전체
+#[derive(Clone, Debug, Default)]
+pub struct Diff {
+    /// 첫 파일 이전의 내용 (`git show`의 커밋 정보 등)
+    pub preamble: String,
+    pub files: Vec<FileDiff>,
+}
+
+impl Diff {
+    /// `git diff`/`git show` 패치 출력을 파싱
+    pub fn parse(output: &[u8]) -> Diff {
+        Parser::default().run(output)
+    }
+
+    /// 변경된 파일이 없는지 여부 (커밋 헤더만 있는 diff도 비어 있는 것으로 봄)
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn added_lines(&self) -> usize {
+        self.files.iter().map(FileDiff::added_lines).sum()
+    }
+
+    pub fn removed_lines(&self) -> usize {
+        self.files.iter().map(FileDiff::removed_lines).sum()
+    }
+
+    /// 원본 패치 형식의 텍스트로 직렬화
+    pub fn render(&self) -> String {
+        let mut out = self.preamble.clone();
+        for file in &self.files {
+            file.render(&mut out);
+        }
+        out
+    }
+
+    /// 파일 변경 통계 한 줄 요약
+    pub fn stats_line(&self) -> String {
+        format!("파일 {}개, +{} -{} 라인", self.files.len(), self.added_lines(), self.removed_lines())
+    }
+
+    /// 변경된 파일 목록 (파일별 상태 코드와 추가/삭제 라인 수)
+    pub fn file_list(&self) -> String {
+        let mut list = String::new();
+        for file in &self.files {
+            list.push_str(&format!("{} {} (+{} -{})\n", file.status.code(), file.display_path(), file.added_lines(), file.removed_lines()));
+        }
+        list
+    }
+}
+
+#[derive(Default)]
+struct Parser {
+    diff: Diff,
+    current: Option<FileDiff>,
+    hunk: Option<Hunk>,
+    old_remaining: u32,
+    new_remaining: u32,
+    next_new_lineno: u32,
+}
+
+impl Parser {
+    fn run(mut self, output: &[u8]) -> Diff {
+        let mut lines = output.split(|&b| b == b'\n').peekable();
+        while let Some(raw) = lines.next() {
+            // 마지막 개행 뒤의 빈 조각은 줄이 아님
+            if raw.is_empty() && lines.peek().is_none() {
+                break;
+            }
+            let raw = raw.strip_suffix(b"\r").unwrap_or(raw);
+            self.line(raw);
+        }
+        self.finish_file();
+        self.diff
+    }
+
+    fn line(&mut self, raw: &[u8]) {
+        if self.hunk.is_some() {
+            if self.old_remaining > 0 || self.new_remaining > 0 || raw.starts_with(b"\\") {
+                self.hunk_line(raw);
+                return;
+            }
+            self.finish_hunk();
+        }
+
+        if raw.starts_with(b"diff --git ") || raw.starts_with(b"diff --cc ") || raw.starts_with(b"diff --combined ") {
+            self.finish_file();
+            self.current = Some(file_from_diff_line(raw));
+            return;
+        }
+
+        let Some(file) = self.current.as_mut() else {
+            self.diff.preamble.push_str(&String::from_utf8_lossy(raw));
+            self.diff.preamble.push('\n');
+            return;
+        };
+
+        if raw.starts_with(b"@@") {
+            if let Some((hunk, new_start)) = parse_hunk_header(raw) {
+                self.old_remaining = hunk.old_lines;
+                self.new_remaining = hunk.new_lines;
+                self.next_new_lineno = new_start;
+                self.hunk = Some(hunk);
+                return;
+            }
+        }
+
+        file.header.push(String::from_utf8_lossy(raw).into_owned());
+        apply_extended_header(file, raw);
+    }
+
+    fn hunk_line(&mut self, raw: &[u8]) {
+        let (kind, content) = match raw.first() {
+            Some(b'+') => (LineKind::Added, &raw[1..]),
+            Some(b'-') => (LineKind::Removed, &raw[1..]),
+            Some(b'\\') => (LineKind::NoNewline, &raw[1..]),
+            Some(b' ') => (LineKind::Context, &raw[1..]),
+            // 일부 도구는 빈 컨텍스트 줄의 공백을 제거함
+            _ => (LineKind::Context, raw),
+        };
+
+        if matches!(kind, LineKind::Removed | LineKind::Context) {
+            self.old_remaining = self.old_remaining.saturating_sub(1);
+        }
+        let mut new_lineno = None;
+        if matches!(kind, LineKind::Added | LineKind::Context) {
+            self.new_remaining = self.new_remaining.saturating_sub(1);
+            new_lineno = Some(self.next_new_lineno);
+            self.next_new_lineno += 1;
+        }
+
+        if let Some(hunk) = self.hunk.as_mut() {
+            hunk.lines.push(DiffLine {
+                kind,
+                content: String::from_utf8_lossy(content).into_owned(),
+                new_lineno,
+            });
+        }
+    }
+
+    fn finish_hunk(&mut self) {
+        if let (Some(hunk), Some(file)) = (self.hunk.take(), self.current.as_mut()) {
+            file.hunks.push(hunk);
+        }
+    }
+
+    fn finish_file(&mut self) {
+        self.finish_hunk();
+        if let Some(file) = self.current.take() {
+            self.diff.files.push(file);
+        }
+    }
+}
+
+fn file_from_diff_line(raw: &[u8]) -> FileDiff {
+    let (old_path, new_path) = if let Some(rest) = raw.strip_prefix(b"diff --git ") {
+        parse_diff_git_paths(rest)
+    } else {
+        // 병합 커밋의 combined diff는 경로가 하나만 표시됨
+        let rest = raw.splitn(3, |&b| b == b' ').nth(2).unwrap_or_default();
+        let path = parse_path_token(rest).0;
+        (Some(path.clone()), Some(path))
+    };
+
+    FileDiff {
+        old_path: old_path.map(|path| path_from_bytes(&path)),
+        new_path: new_path.map(|path| path_from_bytes(&path)),
+        status: FileStatus::Modified,
+        binary: false,
+        header: vec![String::from_utf8_lossy(raw).into_owned()],
+        hunks: Vec::new(),
+    }
+}
+
+/// `diff --git a/X b/Y` 줄의 경로 추출
+///
+/// 따옴표가 없고 공백이 포함된 경로는 두 경로가 같을 때만 확실히 나눌 수 있으므로,
+/// 나머지 경우는 이후의 `rename from`/`---`/`+++` 헤더가 경로를 확정함.
+fn parse_diff_git_paths(rest: &[u8]) -> (Option<Vec<u8>>, Option<Vec<u8>>) {
+    if rest.first() == Some(&b'"') {
+        let (old, remaining) = parse_path_token(rest);
+        let (new, _) = parse_path_token(trim_leading_space(remaining));
+        return (strip_prefix_dir(old, b"a/"), strip_prefix_dir(new, b"b/"));
+    }
+
+    // 같은 경로라면 "a/P b/P" 형태이므로 정확히 절반에서 나뉨
+    if rest.len() % 2 == 1 {
+        let half = rest.len() / 2;
+        let (old, new) = (&rest[..half], &rest[half + 1..]);
+        if rest[half] == b' ' && old.starts_with(b"a/") && new.starts_with(b"b/") && old[2..] == new[2..] {
+            return (Some(old[2..].to_vec()), Some(new[2..].to_vec()));
+        }
+    }
+
+    match rest.windows(3).position(|window| window == b" b/") {
+        Some(split) => (
+            strip_prefix_dir(rest[..split].to_vec(), b"a/"),
+            strip_prefix_dir(rest[split + 1..].to_vec(), b"b/"),
+        ),
+        None => (None, None),
+    }
+}
+
+fn apply_extended_header(file: &mut FileDiff, raw: &[u8]) {
+    if raw.starts_with(b"new file mode") {
+        file.status = FileStatus::Added;
+        file.old_path = None;
+    } else if raw.starts_with(b"deleted file mode") {
+        file.status = FileStatus::Deleted;
+        file.new_path = None;
+    } else if let Some(path) = raw.strip_prefix(b"rename from ") {
+        file.status = FileStatus::Renamed;
+        file.old_path = Some(path_from_bytes(&parse_path_token(path).0));
+    } else if let Some(path) = raw.strip_prefix(b"rename to ") {
+        file.status = FileStatus::Renamed;
+        file.new_path = Some(path_from_bytes(&parse_path_token(path).0));
+    } else if let Some(path) = raw.strip_prefix(b"copy from ") {
+        file.status = FileStatus::Copied;
+        file.old_path = Some(path_from_bytes(&parse_path_token(path).0));
+    } else if let Some(path) = raw.strip_prefix(b"copy to ") {
+        file.status = FileStatus::Copied;
+        file.new_path = Some(path_from_bytes(&parse_path_token(path).0));
+    } else if let Some(path) = raw.strip_prefix(b"--- ") {
+        if let Some(path) = patch_header_path(path, b"a/") {
+            file.old_path = Some(path_from_bytes(&path));
+        }
+    } else if let Some(path) = raw.strip_prefix(b"+++ ") {
+        if let Some(path) = patch_header_path(path, b"b/") {
+            file.new_path = Some(path_from_bytes(&path));
+        }
+    } else if raw.starts_with(b"Binary files ") || raw.starts_with(b"GIT binary patch") {
+        file.binary = true;
+    }
+}
+
+/// `--- a/path` / `+++ b/path` 헤더의 경로 (`/dev/null`이면 None)
+fn patch_header_path(value: &[u8], prefix: &[u8]) -> Option<Vec<u8>> {
+    // 공백이 포함된 경로 뒤에는 탭이 붙음
+    let value = value.strip_suffix(b"\t").unwrap_or(value);
+    if value == b"/dev/null" {
+        return None;
+    }
+    let (path, _) = parse_path_token(value);
+    strip_prefix_dir(path, prefix)
+}
+
+fn strip_prefix_dir(path: Vec<u8>, prefix: &[u8]) -> Option<Vec<u8>> {
+    match path.strip_prefix(prefix) {
+        Some(stripped) => Some(stripped.to_vec()),
+        None => Some(path),
+    }
+}
+
+fn trim_leading_space(value: &[u8]) -> &[u8] {
+    let start = value.iter().position(|&b| b != b' ').unwrap_or(value.len());
+    &value[start..]
+}
+
+/// 경로 토큰 하나를 파싱하고 나머지를 반환 (git의 C 스타일 따옴표 처리 포함)
+fn parse_path_token(value: &[u8]) -> (Vec<u8>, &[u8]) {
+    if value.first() != Some(&b'"') {
+        return (value.to_vec(), &[]);
+    }
+
+    let mut path = Vec::new();
+    let mut i = 1;
+    while i < value.len() {
+        match value[i] {
+            b'"' => return (path, &value[i + 1..]),
+            b'\\' if i + 1 < value.len() => {
+                i += 1;
+                match value[i] {
+                    b'n' => path.push(b'\n'),
+                    b't' => path.push(b'\t'),
+                    b'r' => path.push(b'\r'),
+                    b'a' => path.push(0x07),
+                    b'b' => path.push(0x08),
+                    b'f' => path.push(0x0c),
+                    b'v' => path.push(0x0b),
+                    digit @ b'0'..=b'7' => {
+                        let mut byte = u32::from(digit - b'0');
+                        for _ in 0..2 {
+                            match value.get(i + 1) {
+                                Some(&next @ b'0'..=b'7') => {
+                                    byte = byte * 8 + u32::from(next - b'0');
+                                    i += 1;
+                                }
+                                _ => break,
+                            }
+                        }
+                        path.push(byte as u8);
+                    }
+                    other => path.push(other),
+                }
+            }
+            other => path.push(other),
+        }
+        i += 1;
+    }
+    (path, &[])
+}
+
+/// hunk 헤더를 파싱하고 새 파일 기준 시작 줄 번호를 함께 반환
+fn parse_hunk_header(raw: &[u8]) -> Option<(Hunk, u32)> {
+    let header = String::from_utf8_lossy(raw).into_owned();
+    let mut parts = header.strip_prefix("@@ ")?.split(' ');
+    let (_, old_lines) = parse_range(parts.next()?.strip_prefix('-')?)?;
+    let (new_start, new_lines) = parse_range(parts.next()?.strip_prefix('+')?)?;
+
+    let hunk = Hunk {
+        header,
+        old_lines,
+        new_lines,
+        lines: Vec::new(),
+    };
+    Some((hunk, new_start))
+}
+
+fn parse_range(range: &str) -> Option<(u32, u32)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+#[cfg(unix)]
+pub fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    PathBuf::from(OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(unix))]
+pub fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMIT: &str = "\
+commit 1234567890abcdef1234567890abcdef12345678
+Author:     Jane <jane@example.com>
+
+    Rename player
+
+diff --git a/src/old.js b/src/new.js
+similarity index 90%
+rename from src/old.js
+rename to src/new.js
+index 1111111..2222222 100644
+--- a/src/old.js
++++ b/src/new.js
+@@ -1,3 +1,3 @@
+ const a = 1;
+-const b = 2;
++const b = 3;
+ export { a, b };
+diff --git a/logo.png b/logo.png
+new file mode 100644
+index 0000000..3333333
+Binary files /dev/null and b/logo.png differ
+diff --git a/README b/README
+deleted file mode 100644
+index 4444444..0000000
+--- a/README
++++ /dev/null
+@@ -1 +0,0 @@
+-hello
+\\ No newline at end of file
+";
+
+    #[test]
+    fn parses_commit_header_and_files() {
+        let diff = Diff::parse(COMMIT.as_bytes());
+        assert!(diff.preamble.starts_with("commit 1234567890"));
+        assert_eq!(diff.files.len(), 3);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.render(), COMMIT);
+    }
+
+    #[test]
+    fn parses_rename() {
+        let diff = Diff::parse(COMMIT.as_bytes());
+        let file = &diff.files[0];
+        assert_eq!(file.status, FileStatus::Renamed);
+        assert_eq!(file.old_path.as_deref(), Some(Path::new("src/old.js")));
+        assert_eq!(file.new_path.as_deref(), Some(Path::new("src/new.js")));
+        assert_eq!(file.display_path(), "src/old.js → src/new.js");
+        assert_eq!(file.similarity(), Some(90));
+        assert_eq!((file.added_lines(), file.removed_lines()), (1, 1));
+        let added = file.hunks[0].lines.iter().find(|line| line.kind == LineKind::Added).unwrap();
+        assert_eq!((added.content.as_str(), added.new_lineno), ("const b = 3;", Some(2)));
+    }
+
+    #[test]
+    fn parses_binary_file() {
+        let diff = Diff::parse(COMMIT.as_bytes());
+        let file = &diff.files[1];
+        assert_eq!(file.status, FileStatus::Added);
+        assert!(file.binary);
+        assert_eq!(file.old_path, None);
+        assert_eq!(file.path(), Path::new("logo.png"));
+        assert!(file.hunks.is_empty());
+    }
+
+    #[test]
+    fn parses_no_newline_marker() {
+        let diff = Diff::parse(COMMIT.as_bytes());
+        let file = &diff.files[2];
+        assert_eq!(file.status, FileStatus::Deleted);
+        assert_eq!(file.new_path, None);
+        let kinds: Vec<LineKind> = file.hunks[0].lines.iter().map(|line| line.kind).collect();
+        assert_eq!(kinds, [LineKind::Removed, LineKind::NoNewline]);
+        assert_eq!(file.removed_lines(), 1);
+    }
+
+    #[test]
+    fn header_only_diff_is_empty() {
+        let diff = Diff::parse(b"commit 1234567\nAuthor: Jane <jane@example.com>\n\n    Merge branch\n");
+        assert!(diff.files.is_empty());
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn parses_quoted_paths() {
+        let diff = Diff::parse(b"diff --git \"a/caf\\303\\251 x.js\" \"b/caf\\303\\251 x.js\"\nindex 1..2 100644\n");
+        assert_eq!(diff.files[0].path(), Path::new("café x.js"));
+    }
+}
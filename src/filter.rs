@@ -0,0 +1,274 @@
+use anyhow::{Context, Result};
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
+
+use crate::config::FilterConfig;
+use crate::diff::Diff;
+
+/// 기본 제외 패턴과 제외 사유
+const DEFAULT_EXCLUDES: &[(&str, &str)] = &[
+    // Lock files
+    ("package-lock.json", "npm lock 파일"),
+    ("yarn.lock", "yarn lock 파일"),
+    ("pnpm-lock.yaml", "pnpm lock 파일"),
+    ("composer.lock", "composer lock 파일"),
+    ("Gemfile.lock", "ruby lock 파일"),
+    ("poetry.lock", "python poetry lock 파일"),
+    ("Pipfile.lock", "python pipenv lock 파일"),
+    ("go.sum", "go modules checksum"),
+
+    // Generated/compiled files
+    ("*.min.js", "압축된 JS 파일"),
+    ("*.min.css", "압축된 CSS 파일"),
+    ("*.bundle.js", "번들 JS 파일"),
+    ("*.bundle.css", "번들 CSS 파일"),
+
+    // Build directories
+    ("dist/", "빌드 결과물"),
+    ("build/", "빌드 결과물"),
+    ("output/", "빌드 결과물"),
+    ("out/", "빌드 결과물"),
+
+    // Documentation auto-generated
+    ("CHANGELOG.md", "자동 생성 문서"),
+
+    // IDE/Editor files
+    (".vscode/", "IDE 설정"),
+    (".idea/", "IDE 설정"),
+
+    // OS files
+    (".DS_Store", "OS 파일"),
+    ("Thumbs.db", "OS 파일"),
+
+    // Source maps
+    ("*.json.map", "소스맵"),
+    ("*.js.map", "소스맵"),
+    ("*.css.map", "소스맵"),
+];
+
+#[derive(Clone)]
+struct Rule {
+    /// 사용자가 작성한 원래 패턴
+    pattern: String,
+    /// gitignore 규칙에 맞춰 정규화한 glob
+    glob: String,
+    reason: String,
+}
+
+/// 분석에서 제외된 파일과 사유
+pub struct ExcludedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+/// 파일 제외 규칙. git pathspec과 diff 후처리가 같은 규칙을 사용함
+#[derive(Clone)]
+pub struct DiffFilter {
+    excludes: Vec<Rule>,
+    includes: Vec<Rule>,
+    exclude_set: GlobSet,
+    include_set: GlobSet,
+}
+
+impl DiffFilter {
+    /// 설정 파일과 CLI 인자의 패턴을 합쳐 규칙 생성
+    pub fn new(config: &FilterConfig, extra_excludes: &[String], extra_includes: &[String]) -> Result<Self> {
+        let mut excludes = Vec::new();
+        if config.default_excludes {
+            for (pattern, reason) in DEFAULT_EXCLUDES {
+                excludes.push(Rule::new(pattern, format!("기본 제외 규칙: {}", reason)));
+            }
+        }
+        for pattern in &config.exclude {
+            excludes.push(Rule::new(pattern, "설정 파일 exclude 규칙".to_string()));
+        }
+        for pattern in extra_excludes {
+            excludes.push(Rule::new(pattern, "--exclude 인자".to_string()));
+        }
+
+        let includes: Vec<Rule> = config
+            .include
+            .iter()
+            .chain(extra_includes)
+            .map(|pattern| Rule::new(pattern, String::new()))
+            .collect();
+
+        Ok(Self {
+            exclude_set: build_glob_set(&excludes)?,
+            include_set: build_glob_set(&includes)?,
+            excludes,
+            includes,
+        })
+    }
+
+    /// 파일이 제외 대상이면 사유를 반환
+    pub fn exclusion_reason(&self, path: &Path) -> Option<String> {
+        if !self.includes.is_empty() && !self.include_set.is_match(path) {
+            return Some("include 패턴에 해당하지 않음".to_string());
+        }
+
+        self.exclude_set
+            .matches(path)
+            .first()
+            .map(|&index| {
+                let rule = &self.excludes[index];
+                format!("`{}` ({})", rule.pattern, rule.reason)
+            })
+    }
+
+    /// git diff/show에 넘길 pathspec 목록
+    pub fn pathspecs(&self) -> Vec<String> {
+        let mut pathspecs: Vec<String> = self
+            .includes
+            .iter()
+            .map(|rule| format!(":(glob){}", rule.glob))
+            .collect();
+        pathspecs.extend(self.excludes.iter().map(|rule| format!(":(exclude,glob){}", rule.glob)));
+        pathspecs
+    }
+
+    /// diff에서 제외 대상 파일을 제거하고 제외된 전체 목록을 반환
+    ///
+    /// `changed_paths`는 pathspec 없이 얻은 전체 변경 파일 목록으로, git 단계에서 이미
+    /// 제외된 파일도 보고서에 포함시키기 위해 사용함. 이름 변경은 이전/새 경로 중 하나라도
+    /// 제외 대상이면 제외함.
+    pub fn apply(&self, diff: &mut Diff, changed_paths: &[PathBuf]) -> Vec<ExcludedFile> {
+        let mut excluded: Vec<ExcludedFile> = changed_paths
+            .iter()
+            .filter_map(|path| {
+                self.exclusion_reason(path).map(|reason| ExcludedFile {
+                    path: path.display().to_string(),
+                    reason,
+                })
+            })
+            .collect();
+
+        diff.files.retain(|file| {
+            let reason = [&file.old_path, &file.new_path]
+                .into_iter()
+                .flatten()
+                .find_map(|path| self.exclusion_reason(path));
+
+            match reason {
+                Some(reason) => {
+                    // 전체 변경 목록의 같은 파일(이름 변경이면 이전/새 경로)은 diff의 경로 표시 하나로 대체
+                    let paths: Vec<String> = [&file.old_path, &file.new_path]
+                        .into_iter()
+                        .flatten()
+                        .map(|path| path.display().to_string())
+                        .collect();
+                    let first = excluded.iter().position(|entry| paths.contains(&entry.path));
+                    let mut index = 0;
+                    excluded.retain(|entry| {
+                        let keep = Some(index) == first || !paths.contains(&entry.path);
+                        index += 1;
+                        keep
+                    });
+
+                    let entry = ExcludedFile { path: file.display_path(), reason };
+                    match first {
+                        Some(index) => excluded[index] = entry,
+                        None => excluded.push(entry),
+                    }
+                    false
+                }
+                None => true,
+            }
+        });
+        excluded
+    }
+}
+
+impl Rule {
+    fn new(pattern: &str, reason: String) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            glob: normalize_pattern(pattern),
+            reason,
+        }
+    }
+}
+
+/// gitignore와 같은 방식으로 패턴을 저장소 루트 기준 glob으로 변환
+///
+/// - `/`가 없는 패턴(`*.min.js`, `yarn.lock`)은 모든 디렉토리에서 일치
+/// - `/`로 끝나는 패턴(`dist/`)은 해당 디렉토리 아래 전체와 일치
+/// - `/`로 시작하거나 중간에 `/`가 있는 패턴은 루트 기준
+fn normalize_pattern(pattern: &str) -> String {
+    let pattern = pattern.trim();
+    let (pattern, directory) = match pattern.strip_suffix('/') {
+        Some(stripped) => (stripped, true),
+        None => (pattern, false),
+    };
+
+    let mut glob = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_string(),
+        None if pattern.contains('/') => pattern.to_string(),
+        None => format!("**/{}", pattern),
+    };
+    if directory {
+        glob.push_str("/**");
+    }
+    glob
+}
+
+fn build_glob_set(rules: &[Rule]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for rule in rules {
+        builder.add(compile_glob(rule)?);
+    }
+    Ok(builder.build()?)
+}
+
+fn compile_glob(rule: &Rule) -> Result<Glob> {
+    GlobBuilder::new(&rule.glob)
+        .literal_separator(true)
+        .build()
+        .with_context(|| format!("잘못된 glob 패턴입니다: {}", rule.pattern))
+}
+
+/// 제외된 파일 목록을 사람이 읽을 수 있는 보고서로 변환
+pub fn format_excluded_report(excluded: &[ExcludedFile]) -> String {
+    let mut report = format!("# 분석에서 제외된 파일 ({}개)\n\n", excluded.len());
+    for file in excluded {
+        report.push_str(&format!("- {}: {}\n", file.path, file.reason));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RENAME: &str = "\
+diff --git a/docs/old.md b/docs/new.md
+similarity index 90%
+rename from docs/old.md
+rename to docs/new.md
+--- a/docs/old.md
++++ b/docs/new.md
+@@ -1 +1 @@
+-old
++new
+diff --git a/src/lib.js b/src/lib.js
+--- a/src/lib.js
++++ b/src/lib.js
+@@ -1 +1 @@
+-a
++b
+";
+
+    #[test]
+    fn renamed_excluded_file_is_listed_once() {
+        let filter = DiffFilter::new(&FilterConfig::default(), &["*.md".to_string()], &[]).unwrap();
+        let mut diff = Diff::parse(RENAME.as_bytes());
+        let changed = [PathBuf::from("docs/new.md"), PathBuf::from("src/lib.js"), PathBuf::from("notes.md")];
+
+        let excluded = filter.apply(&mut diff, &changed);
+
+        let paths: Vec<&str> = excluded.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, vec!["docs/old.md → docs/new.md", "notes.md"]);
+        assert_eq!(diff.files.len(), 1);
+        assert_eq!(diff.files[0].path(), Path::new("src/lib.js"));
+    }
+}
@@ -0,0 +1,312 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::profile::RenderedProfile;
+use crate::prompt::AnalysisTarget;
+
+/// 사이드 이펙트 리스크 수준
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Risk {
+    High,
+    Medium,
+    Low,
+    /// 보고서에서 리스크를 찾지 못함 (구조화 응답에는 사용하지 않음)
+    #[serde(skip)]
+    Unknown,
+}
+
+impl Risk {
+    /// 마크다운 보고서에서 리스크를 찾을 때 쓰는 단어
+    const LEVELS: [(&'static str, Risk); 3] = [("높음", Risk::High), ("중간", Risk::Medium), ("낮음", Risk::Low)];
+
+    /// 보고서의 "사이드 이펙트 리스크" 항목에서 리스크 수준 추출
+    ///
+    /// 해당 항목이 없으면 리스크 수준이 하나만 적힌 첫 "리스크" 줄을 사용
+    pub fn from_report(report: &str) -> Risk {
+        let level_of = |line: &str| {
+            let mut found = Risk::LEVELS.iter().filter(|(word, _)| line.contains(word));
+            match (found.next(), found.next()) {
+                (Some((_, risk)), None) => Some(*risk),
+                _ => None,
+            }
+        };
+
+        let risk_lines = || report.lines().filter(|line| line.contains("리스크"));
+        risk_lines()
+            .filter(|line| line.contains("사이드 이펙트 리스크"))
+            .find_map(level_of)
+            .or_else(|| risk_lines().find_map(level_of))
+            .unwrap_or(Risk::Unknown)
+    }
+
+    /// 기계가 읽는 출력에 쓰는 이름
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Risk::High => "high",
+            Risk::Medium => "medium",
+            Risk::Low => "low",
+            Risk::Unknown => "unknown",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Risk::High => "🔴 높음",
+            Risk::Medium => "🟡 중간",
+            Risk::Low => "🟢 낮음",
+            Risk::Unknown => "알 수 없음",
+        }
+    }
+}
+
+/// 발견 사항 분류
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Compatibility,
+    Behavior,
+    Api,
+    Performance,
+    Media,
+    Security,
+    Other,
+}
+
+impl Category {
+    const ALL: [&'static str; 7] = ["compatibility", "behavior", "api", "performance", "media", "security", "other"];
+
+    fn label(self) -> &'static str {
+        match self {
+            Category::Compatibility => "호환성",
+            Category::Behavior => "동작 변경",
+            Category::Api => "API 변경",
+            Category::Performance => "성능",
+            Category::Media => "미디어 재생",
+            Category::Security => "보안",
+            Category::Other => "기타",
+        }
+    }
+}
+
+/// 업데이트 권장도
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Recommendation {
+    Immediate,
+    AfterTesting,
+    Careful,
+}
+
+impl Recommendation {
+    fn label(self) -> &'static str {
+        match self {
+            Recommendation::Immediate => "즉시",
+            Recommendation::AfterTesting => "테스트 후",
+            Recommendation::Careful => "신중히",
+        }
+    }
+}
+
+/// 변경사항 하나에 대한 발견 사항
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Finding {
+    pub file: String,
+    pub line: Option<u32>,
+    pub category: Category,
+    pub risk: Risk,
+    pub title: String,
+    pub description: String,
+    /// 문제가 되는 코드
+    pub code: Option<String>,
+    /// 영향을 받는 브라우저/런타임 버전 (예: "Chrome 38-49")
+    pub affected_browsers: Vec<String>,
+    pub suggested_fix: Option<String>,
+}
+
+/// 구조화 출력 모드의 분석 결과 (`_findings.json`)
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FindingsReport {
+    pub summary: String,
+    pub change_scale: String,
+    pub overall_risk: Risk,
+    pub update_recommendation: Recommendation,
+    pub findings: Vec<Finding>,
+    pub test_scenarios: Vec<String>,
+    pub recommendations: Vec<String>,
+}
+
+/// 모델에 요청하는 JSON 스키마 (OpenAI strict 모드 규칙에 맞춰 모든 필드 필수, null 허용으로 선택 표현)
+pub fn json_schema() -> Value {
+    let risks = json!(["high", "medium", "low"]);
+    let nullable_string = json!({ "type": ["string", "null"] });
+    let string_list = json!({ "type": "array", "items": { "type": "string" } });
+
+    json!({
+        "type": "object",
+        "additionalProperties": false,
+        "required": ["summary", "change_scale", "overall_risk", "update_recommendation", "findings", "test_scenarios", "recommendations"],
+        "properties": {
+            "summary": { "type": "string" },
+            "change_scale": { "type": "string" },
+            "overall_risk": { "type": "string", "enum": risks },
+            "update_recommendation": { "type": "string", "enum": ["immediate", "after_testing", "careful"] },
+            "findings": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "required": ["file", "line", "category", "risk", "title", "description", "code", "affected_browsers", "suggested_fix"],
+                    "properties": {
+                        "file": { "type": "string" },
+                        "line": { "type": ["integer", "null"] },
+                        "category": { "type": "string", "enum": Category::ALL },
+                        "risk": { "type": "string", "enum": risks },
+                        "title": { "type": "string" },
+                        "description": { "type": "string" },
+                        "code": nullable_string,
+                        "affected_browsers": string_list,
+                        "suggested_fix": nullable_string,
+                    },
+                },
+            },
+            "test_scenarios": string_list,
+            "recommendations": string_list,
+        },
+    })
+}
+
+impl FindingsReport {
+    /// 모델 응답을 검증하며 파싱 (코드 블록으로 감싼 응답도 허용)
+    pub fn parse(response: &str) -> Result<Self> {
+        let trimmed = response.trim();
+        let json = trimmed
+            .strip_prefix("```json")
+            .or_else(|| trimmed.strip_prefix("```"))
+            .and_then(|rest| rest.strip_suffix("```"))
+            .unwrap_or(trimmed);
+
+        serde_json::from_str(json).with_context(|| {
+            let preview: String = json.chars().take(200).collect();
+            format!("구조화 응답이 스키마와 맞지 않습니다: {}", preview)
+        })
+    }
+
+    /// 기존 보고서 형식의 마크다운으로 작성
+    pub fn render_markdown(&self, target: &AnalysisTarget<'_>, profile: &RenderedProfile) -> String {
+        let mut report = format!("# {}\n\n", target.report_title());
+        report.push_str("## 📊 개요\n");
+        report.push_str(&format!("- 분석 대상: {}\n", target.overview_target()));
+        report.push_str("- 분석 목적: 라이브러리 사용자의 사이드 이펙트 방지\n");
+        report.push_str(&format!("- 분석 기준: {}\n", profile.overview_criteria));
+        report.push_str(&format!("- 요약: {}\n\n", self.summary.trim()));
+
+        report.push_str(&self.findings_section());
+
+        report.push_str(&format!("## ⚠️ {}\n\n", target.caution_heading()));
+        report.push_str(&bullet_list(&self.test_scenarios));
+
+        // 리스크가 높은 발견 사항의 파일
+        let mut key_files: Vec<&str> = Vec::new();
+        for finding in self.findings.iter().filter(|finding| finding.risk <= Risk::Medium) {
+            if !key_files.contains(&finding.file.as_str()) {
+                key_files.push(&finding.file);
+            }
+        }
+        let key_files = match key_files.is_empty() {
+            true => "없음".to_string(),
+            false => key_files.iter().map(|file| format!("`{}`", file)).collect::<Vec<_>>().join(", "),
+        };
+
+        report.push_str("## 📈 종합 평가\n");
+        report.push_str(&format!("- 변경 규모: {}\n", self.change_scale));
+        report.push_str(&format!("- 사이드 이펙트 리스크: {}\n", self.overall_risk.label()));
+        report.push_str(&format!("- 업데이트 권장도: {}\n", self.update_recommendation.label()));
+        report.push_str(&format!("- 핵심 확인 대상 파일들: {}\n\n", key_files));
+
+        report.push_str("## 💡 결론 및 권장사항\n\n");
+        report.push_str(&bullet_list(&self.recommendations));
+        report
+    }
+
+    /// 발견 사항 요약표와 항목별 상세 (리스크가 높은 순)
+    fn findings_section(&self) -> String {
+        let mut findings: Vec<&Finding> = self.findings.iter().collect();
+        findings.sort_by_key(|finding| finding.risk);
+
+        let mut section = format!("## 🚨 발견 사항 ({}건)\n\n", findings.len());
+        if findings.is_empty() {
+            section.push_str("사용자에게 영향을 주는 문제가 발견되지 않았습니다.\n\n");
+            return section;
+        }
+
+        section.push_str("| # | 리스크 | 분류 | 위치 | 영향 버전 | 내용 |\n|---|---|---|---|---|---|\n");
+        for (index, finding) in findings.iter().enumerate() {
+            section.push_str(&format!(
+                "| {} | {} | {} | `{}` | {} | {} |\n",
+                index + 1,
+                finding.risk.label(),
+                finding.category.label(),
+                finding.location(),
+                affected_label(finding).replace('|', "\\|"),
+                finding.title.replace('|', "\\|")
+            ));
+        }
+        section.push('\n');
+
+        for (index, finding) in findings.iter().enumerate() {
+            section.push_str(&format!("### {}. {}\n\n", index + 1, finding.title));
+            section.push_str(&format!("- **위치**: `{}`\n", finding.location()));
+            section.push_str(&format!("- **분류**: {} / **리스크**: {}\n", finding.category.label(), finding.risk.label()));
+            section.push_str(&format!("- **영향 버전**: {}\n\n", affected_label(finding)));
+            section.push_str(&format!("**문제점**: {}\n\n", finding.description.trim()));
+            if let Some(code) = &finding.code {
+                section.push_str(&format!("**문제 코드**:\n{}\n\n", code_block(code)));
+            }
+            if let Some(fix) = &finding.suggested_fix {
+                section.push_str(&format!("**제안 수정**:\n{}\n\n", code_block(fix)));
+            }
+        }
+        section
+    }
+}
+
+impl Finding {
+    fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.file, line),
+            None => self.file.clone(),
+        }
+    }
+}
+
+fn affected_label(finding: &Finding) -> String {
+    match finding.affected_browsers.is_empty() {
+        true => "-".to_string(),
+        false => finding.affected_browsers.join(", "),
+    }
+}
+
+fn bullet_list(items: &[String]) -> String {
+    if items.is_empty() {
+        return "- 없음\n\n".to_string();
+    }
+    let mut list: String = items.iter().map(|item| format!("- {}\n", item.trim())).collect();
+    list.push('\n');
+    list
+}
+
+/// 내용에 포함된 백틱보다 긴 펜스로 감싼 코드 블록
+fn code_block(code: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in code.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let fence = "`".repeat((longest + 1).max(3));
+    format!("{}\n{}\n{}", fence, code.trim_end(), fence)
+}
@@ -0,0 +1,220 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::findings::Risk;
+use crate::series::{CommitOutcome, SeriesEntry};
+
+/// 실행 오류(git, API 실패 등)의 종료 코드
+pub const EXIT_ERROR: i32 = 1;
+/// 기준 이상의 리스크가 발견되었을 때의 종료 코드
+pub const EXIT_RISK_EXCEEDED: i32 = 3;
+/// 보고서에서 리스크를 판단할 수 없을 때의 종료 코드 (분석에 실패한 커밋이 있는 경우 포함)
+pub const EXIT_RISK_UNKNOWN: i32 = 4;
+
+/// `--fail-on` 기준 리스크 (이 수준 이상이면 실패)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    /// 리스크가 높음이면 실패
+    High,
+    /// 리스크가 중간 이상이면 실패
+    Medium,
+}
+
+impl FailOn {
+    fn threshold(self) -> Risk {
+        match self {
+            FailOn::High => Risk::High,
+            FailOn::Medium => Risk::Medium,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        self.threshold().as_str()
+    }
+
+    fn evaluate(self, risk: Risk) -> GateStatus {
+        match risk {
+            Risk::Unknown => GateStatus::Unknown,
+            risk if risk <= self.threshold() => GateStatus::Failed,
+            _ => GateStatus::Passed,
+        }
+    }
+}
+
+/// 게이트 판정 (JSON의 `status`)
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GateStatus {
+    /// 기준 미만이거나 분석할 변경 없음 (종료 코드 0)
+    Passed,
+    /// 기준 이상의 리스크 (종료 코드 3)
+    Failed,
+    /// 리스크를 판단할 수 없음 (`Risk::Unknown`이거나 분석에 실패한 커밋이 있음, 종료 코드 4)
+    Unknown,
+    /// 분석을 끝내지 못한 실행 오류 (종료 코드 1)
+    Error,
+}
+
+#[derive(Serialize)]
+pub struct CommitVerdict {
+    commit: String,
+    risk: &'static str,
+}
+
+/// CI 게이트 판정 결과 (stdout 마지막 줄에 JSON 한 줄로 출력)
+#[derive(Serialize)]
+pub struct GateResult {
+    pub status: GateStatus,
+    /// 판정에 사용한 리스크 (`high`, `medium`, `low`, `unknown`, 분석할 변경이 없으면 `none`)
+    pub risk: &'static str,
+    pub fail_on: &'static str,
+    pub target: String,
+    /// 구조화 출력 모드의 발견 사항 수
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub findings: Option<usize>,
+    /// 보고서 파일 (커밋 시리즈 모드는 인덱스 파일)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<String>,
+    /// 커밋 시리즈 모드의 커밋별 리스크
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub commits: Vec<CommitVerdict>,
+    /// 실행 오류 메시지
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl GateResult {
+    /// 보고서 하나의 리스크로 판정
+    pub fn for_report(fail_on: FailOn, target: String, risk: Risk, findings: Option<usize>, report: String) -> Self {
+        Self {
+            status: fail_on.evaluate(risk),
+            risk: risk.as_str(),
+            fail_on: fail_on.as_str(),
+            target,
+            findings,
+            report: Some(report),
+            commits: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// 분석할 변경이 없으면 통과
+    pub fn no_changes(fail_on: FailOn, target: String) -> Self {
+        Self {
+            status: GateStatus::Passed,
+            risk: "none",
+            fail_on: fail_on.as_str(),
+            target,
+            findings: None,
+            report: None,
+            commits: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// 분석을 끝내지 못함 (CI가 리스크 차단과 도구 오류를 구분할 수 있도록 판정 결과로 알림)
+    pub fn error(fail_on: FailOn, target: String, error: &anyhow::Error) -> Self {
+        Self {
+            status: GateStatus::Error,
+            risk: Risk::Unknown.as_str(),
+            fail_on: fail_on.as_str(),
+            target,
+            findings: None,
+            report: None,
+            commits: Vec::new(),
+            error: Some(format!("{:#}", error)),
+        }
+    }
+
+    /// 커밋별 리스크 중 가장 높은 리스크로 판정
+    ///
+    /// 기준을 넘는 커밋이 없더라도 리스크를 판단할 수 없거나 분석에 실패한 커밋이 있으면 판단 불가로 처리함
+    pub fn for_series(fail_on: FailOn, target: String, entries: &[SeriesEntry], index: String) -> Self {
+        let mut commits = Vec::new();
+        let mut highest: Option<Risk> = None;
+        let mut undetermined = false;
+        for entry in entries {
+            let risk = match &entry.outcome {
+                CommitOutcome::Analyzed { risk, .. } => risk.as_str(),
+                CommitOutcome::Empty => "none",
+                CommitOutcome::Failed(_) => "failed",
+            };
+            match &entry.outcome {
+                CommitOutcome::Analyzed { risk: Risk::Unknown, .. } | CommitOutcome::Failed(_) => undetermined = true,
+                CommitOutcome::Analyzed { risk, .. } => highest = Some(highest.map_or(*risk, |highest| highest.min(*risk))),
+                CommitOutcome::Empty => {}
+            }
+            commits.push(CommitVerdict { commit: entry.commit.short_sha().to_string(), risk });
+        }
+
+        let status = match highest.map(|risk| fail_on.evaluate(risk)) {
+            Some(GateStatus::Failed) => GateStatus::Failed,
+            _ if undetermined => GateStatus::Unknown,
+            _ => GateStatus::Passed,
+        };
+        let risk = match (highest, undetermined) {
+            (Some(risk), _) => risk.as_str(),
+            (None, true) => Risk::Unknown.as_str(),
+            (None, false) => "none",
+        };
+        Self {
+            status,
+            risk,
+            fail_on: fail_on.as_str(),
+            target,
+            findings: None,
+            report: Some(index),
+            commits,
+            error: None,
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self.status {
+            GateStatus::Passed => 0,
+            GateStatus::Failed => EXIT_RISK_EXCEEDED,
+            GateStatus::Unknown => EXIT_RISK_UNKNOWN,
+            GateStatus::Error => EXIT_ERROR,
+        }
+    }
+
+    /// 판정 결과를 한 줄로 알리고, 마지막 줄에 JSON 요약 출력
+    pub fn print(&self) {
+        let verdict = match self.status {
+            GateStatus::Passed => "통과",
+            GateStatus::Failed => "실패",
+            GateStatus::Unknown => "판단 불가",
+            GateStatus::Error => "실행 오류",
+        };
+        println!("\n리스크 게이트 (--fail-on {}): {} (리스크: {})", self.fail_on, verdict, self.risk);
+        match serde_json::to_string(self) {
+            Ok(json) => println!("{}", json),
+            Err(error) => println!("게이트 결과 직렬화 실패: {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_by_risk() {
+        let exit_code = |fail_on, risk| GateResult::for_report(fail_on, String::new(), risk, None, String::new()).exit_code();
+        assert_eq!(exit_code(FailOn::High, Risk::High), EXIT_RISK_EXCEEDED);
+        assert_eq!(exit_code(FailOn::High, Risk::Medium), 0);
+        assert_eq!(exit_code(FailOn::Medium, Risk::Medium), EXIT_RISK_EXCEEDED);
+        assert_eq!(exit_code(FailOn::Medium, Risk::Low), 0);
+        assert_eq!(exit_code(FailOn::High, Risk::Unknown), EXIT_RISK_UNKNOWN);
+    }
+
+    #[test]
+    fn error_result_is_serialized() {
+        let result = GateResult::error(FailOn::High, "v1 → v2".to_string(), &anyhow::anyhow!("API 키 없음"));
+        assert_eq!(result.exit_code(), EXIT_ERROR);
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            r#"{"status":"error","risk":"unknown","fail_on":"high","target":"v1 → v2","error":"API 키 없음"}"#
+        );
+    }
+}
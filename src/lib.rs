@@ -0,0 +1,37 @@
+//! Git 변경사항을 LLM으로 분석하는 라이브러리
+//!
+//! `git-diff-analyzer` 명령은 이 라이브러리를 감싼 얇은 CLI이며, 다른 Rust 서비스도 같은 흐름으로
+//! 태그 간 변경사항을 분석할 수 있음:
+//! 저장소([`repo::open_backend`])에서 [`DiffSource`]의 diff를 [`DiffFilter`]로 걸러 읽고,
+//! [`Analyzer`]가 [`PromptBuilder`]로 만든 프롬프트로 분석한 결과를 [`ReportWriter`]로 저장
+
+pub mod analyzer;
+pub mod api_surface;
+pub mod cache;
+mod chunk;
+pub mod compat;
+pub mod config;
+pub mod diff;
+pub mod filter;
+pub mod findings;
+pub mod gate;
+pub mod profile;
+pub mod prompt;
+pub mod provider;
+pub mod publish;
+mod relevance;
+pub mod remote;
+pub mod repo;
+pub mod report;
+mod retry;
+pub mod series;
+pub mod source;
+pub mod stats;
+pub mod tags;
+pub mod tokenizer;
+
+pub use analyzer::{Analysis, Analyzer};
+pub use filter::DiffFilter;
+pub use prompt::PromptBuilder;
+pub use report::ReportWriter;
+pub use source::DiffSource;
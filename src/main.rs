@@ -1,723 +1,881 @@
-use clap::Parser;
+use clap::{ArgGroup, Parser, Subcommand};
 use dotenv::dotenv;
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use std::env;
-use std::fs;
-use std::path::Path;
-use std::process::Command;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use anyhow::{Result, anyhow};
-use tokio;
-
-// 프롬프트 공통 부분 상수들
-const ANALYSIS_PURPOSE: &str = "**분석 목적**: 라이브러리를 빌드 후 사용하는 개발자가 버전 업데이트 시 발생할 수 있는 사이드 이펙트를 사전에 파악하여 방지. 미디어 재생 관점에서 영향이 있는 변경사항을 분석.";
-
-const ANALYSIS_CRITERIA: &str = "**분석 기준**:
-- Chromium M38+ 버전 기준 (구체적인 API별 호환성 체크 필수)
-- 라이브러리 빌드 후 사용자에게 실제 영향을 주는 변경사항
-- 코드 스타일, 주석 등 동작에 영향 없는 변경사항은 제외
-- API 변경, 동작 로직 변경, 성능 영향, 최적화 등 실질적 변경사항 중심
-- 사용자 영향이 없더라도 동작 변경이 있으면 반드시 분석
-- 각 변경사항마다 파일명과 실제 코드 변경 내용을 포함";
-
-const API_COMPATIBILITY_CHECKLIST: &str = "**🚨 중요 API 호환성 체크리스트** (반드시 확인):
-- **HTMLMediaElement.play()**: Chrome 50+에서 Promise 반환, 이전 버전(M38-M49)에서는 void 반환 → .catch() 사용 시 에러!
-- **fetch()**: Chrome 42+ (M38에서는 사용 불가)
-- **Promise**: Chrome 32+ (M38에서 지원)
-- **async/await**: Chrome 55+ (M38에서는 사용 불가)
-- **ResizeObserver**: Chrome 64+ (M38에서는 사용 불가)
-- **IntersectionObserver**: Chrome 51+ (M38에서는 사용 불가)
-- **Object.assign()**: Chrome 45+ (M38에서는 사용 불가)
-- **Array.includes()**: Chrome 47+ (M38에서는 사용 불가)
-- **Array.find()/findIndex()**: Chrome 45+ (M38에서는 사용 불가)
-- **String.includes/startsWith/endsWith**: Chrome 41+ (M38에서는 사용 불가)
-- **Map/Set**: Chrome 38+ (M38에서 지원)
-- **for...of**: Chrome 38+ (M38에서 지원)
-
-**중요**: 코드에서 이런 API들이 사용되면 반드시 브라우저 호환성을 체크하고, 문제가 있으면 **높은 리스크**로 분류하세요!";
-
-const REPORT_FORMAT_SECTIONS: &str = "## 🌐 크로스브라우징 영향 분석 (Chromium M38+ 기준)
-
-실제 동작 변경이 있는 파일들을 분석하여 각 변경사항별로:
-- 변경된 파일명과 구체적인 코드 변경 내용
-- **구체적인 브라우저 호환성 문제** (상기 체크리스트 기준으로 정확히 분석)
-- 호환성 문제가 있다면 **어떤 브라우저 버전에서 에러가 발생하는지** 명시
-- 안전한 코딩 패턴 제시
-
-### 🚨 호환성 경고 (발견 시)
-각 문제별로:
-**문제 코드**: `구체적인 코드`
-**문제점**: Chrome M38-M49에서 HTMLMediaElement.play()는 void를 반환하므로 .catch() 호출 시 TypeError 발생
-**변경점 코드**:
-```javascript
-// 변경점
-const playPromise = media.play();
-
-// 제안 코드
-media.play().catch(/* 에러 처리 */);
-if (playPromise !== undefined) {{
-  playPromise.catch(/* 에러 처리 */);
-}}
-```
-
-## 🎬 미디어 재생 영향 분석
-
-미디어 재생 관련 변경사항이 있다면:
-- 변경된 파일명과 재생 로직 변경 내용
-- 관련 미디어 기술 배경 설명 (코덱, 스트리밍, DRM 등)
-- MediaSource, HTMLMediaElement 등 미디어 API 사용 여부
-- 재생 품질, 성능, 안정성에 미치는 실제 영향
-- 미디어 API 호환성 문제 (위 체크리스트 기준)
-
-## 🔧 라이브러리 사용자 영향 분석
-
-API 변경, 동작 변경, 성능 최적화 등이 있다면:
-- 변경된 파일명과 구체적인 변경 내용
-- 사용자 코드 수정 필요 여부
-- 성능상 개선점 또는 주의사항
-- 호환성 문제 및 구체적인 대응 방안";
-
-const REPORT_FORMAT_FOOTER: &str = "## 📈 종합 평가
-- 변경 규모
-- 사이드 이펙트 리스크
-- 업데이트 권장도 (즉시/테스트 후/신중히)
-- 핵심 확인 대상 파일들
-
-## 💡 결론 및 권장사항
-- 주요 사이드 이펙트 요약
-- 안전한 업데이트 전략
-- 필수 확인 사항
-- **즉시 수정이 필요한 호환성 문제** (발견 시)";
+use git_diff_analyzer::{Analyzer, DiffFilter, DiffSource, ReportWriter};
+use git_diff_analyzer::api_surface::{self, ChangeKind};
+use git_diff_analyzer::cache::ResponseCache;
+use git_diff_analyzer::compat::CompatScanner;
+use git_diff_analyzer::config::{CONFIG_FILE_NAME, DEFAULT_OUTPUT_DIR, FileConfig, ModelConfig, TagsConfig, config_layers, find_config_file, load_config, merge_layers, user_config_file};
+use git_diff_analyzer::findings::Risk;
+use git_diff_analyzer::gate::{FailOn, GateResult};
+use git_diff_analyzer::profile::{DEFAULT_PROFILE, Profile};
+use git_diff_analyzer::publish::{CommentPublisher, Platform, PublishOutcome};
+use git_diff_analyzer::provider::{DryRunProvider, LlmProvider, ProviderKind, RecordedPrompt, RequestOptions, create_provider};
+use git_diff_analyzer::remote::RepoCache;
+use git_diff_analyzer::repo::{BackendKind, RepoBackend, open_backend};
+use git_diff_analyzer::report::compose_summary;
+use git_diff_analyzer::series::{SeriesRunner, format_index};
+use git_diff_analyzer::source::{get_diff, verify_revisions};
+use git_diff_analyzer::stats::DiffStats;
+use git_diff_analyzer::tags::{TagIndex, resolve_tag_shortcuts};
 
 #[derive(Parser)]
 #[command(name = "git-diff-analyzer")]
 #[command(about = "Git diff를 분석하여 변경점을 요약하는 도구")]
-struct Args {
-    /// 프로젝트 이름
-    #[arg(short, long)]
-    project: String,
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
     
-    /// 이전 태그 (커밋 분석 시 선택사항)
-    #[arg(short, long)]
-    from_tag: Option<String>,
+    /// 릴리스 태그의 버전 앞에 붙는 접두사 (여러 번 지정 가능, 기본값: 설정 파일의 [tags] prefixes 또는 "v"와 "")
+    #[arg(long, global = true)]
+    tag_prefix: Vec<String>,
     
-    /// 이후 태그 (커밋 분석 시 선택사항)
-    #[arg(short, long)]
-    to_tag: Option<String>,
+    /// --repo로 받은 clone을 보관할 디렉토리 (기본값: ~/.cache/git-diff-analyzer/repos)
+    #[arg(long, env = "GIT_DIFF_ANALYZER_REPO_CACHE_DIR", global = true)]
+    repo_cache_dir: Option<PathBuf>,
+    
+    /// 저장소를 읽는 방식 (libgit2: 프로세스 안에서 직접 읽음, cli: git 명령 실행, 기본값: libgit2, --repo는 cli)
+    #[arg(long, value_enum, global = true)]
+    git_backend: Option<BackendKind>,
     
-    /// 분석할 커밋 해시 (단일 커밋 분석 시 사용)
+    /// 저장소 설정 파일 경로 (기본값: 프로젝트 루트 또는 현재 디렉토리의 .diffanalyzer.toml, 사용자 설정 위에 적용)
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    
+    /// 응답 캐시 디렉토리 (기본값: ~/.cache/git-diff-analyzer)
+    #[arg(long, env = "GIT_DIFF_ANALYZER_CACHE_DIR", global = true)]
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 두 리비전 사이 또는 커밋되지 않은 변경사항 분석
+    Range(RangeArgs),
+    /// 단일 커밋 분석
+    Commit(CommitArgs),
+    /// 범위의 커밋(git rev-list from..to)을 하나씩 분석하고 인덱스 보고서 생성
+    Series(SeriesArgs),
+    /// 저장소의 릴리스 태그를 semver 순으로 출력
+    Tags(TagsArgs),
+    /// LLM 응답 캐시 관리
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// 적용되는 설정 파일 확인
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// 분석 보고서를 PR/MR 댓글로 게시 (이전에 게시한 댓글이 있으면 수정)
+    Publish(PublishArgs),
+}
+
+/// 분석할 저장소
+#[derive(clap::Args)]
+struct ProjectArgs {
+    /// 프로젝트 이름
     #[arg(short, long)]
-    commit: Option<String>,
+    project: String,
     
     /// 프로젝트 경로 (선택사항, 기본값: ./repositories/{project})
     #[arg(long)]
     path: Option<String>,
+    
+    /// 원격 저장소 URL (캐시 디렉토리에 blob 없는 bare clone을 만들고 다음 실행부터 재사용)
+    #[arg(long, conflicts_with = "path")]
+    repo: Option<String>,
 }
 
-#[derive(Serialize)]
-struct OpenAIRequest {
-    model: String,
-    messages: Vec<Message>,
-    max_tokens: u32,
-    temperature: f32,
+/// 리비전 범위
+#[derive(clap::Args)]
+struct RevisionRange {
+    /// 이전 리비전 (태그, 브랜치, 커밋 SHA, HEAD~N, latest, previous 등). `A...B` 형식이면 --to 없이 범위 지정
+    #[arg(short, long, visible_alias = "from-tag")]
+    from: Option<String>,
+    
+    /// 이후 리비전 (태그, 브랜치, 커밋 SHA, HEAD~N, latest 등)
+    #[arg(short, long, visible_alias = "to-tag")]
+    to: Option<String>,
+    
+    /// 이 태그부터 --to(기본값: HEAD)까지 분석 (v1.2처럼 일부만 적으면 해당 시리즈의 첫 릴리스)
+    #[arg(long, conflicts_with = "from")]
+    since_tag: Option<String>,
+    
+    /// --from과 --to의 공통 조상을 기준으로 비교 (git diff A...B)
+    #[arg(long)]
+    merge_base: bool,
 }
 
-#[derive(Serialize)]
-struct Message {
-    role: String,
-    content: String,
+impl RevisionRange {
+    fn source(&self) -> Result<DiffSource> {
+        if let Some(tag) = &self.since_tag {
+            let to = self.to.clone().unwrap_or_else(|| "HEAD".to_string());
+            return Ok(DiffSource::Range { from: tag.clone(), to, merge_base: self.merge_base });
+        }
+        
+        match (&self.from, &self.to) {
+            (Some(from), Some(to)) => Ok(DiffSource::Range { from: from.clone(), to: to.clone(), merge_base: self.merge_base }),
+            (Some(spec), None) => match DiffSource::parse_range(spec) {
+                Some(DiffSource::Range { from, to, merge_base }) => Ok(DiffSource::Range { from, to, merge_base: merge_base || self.merge_base }),
+                _ => Err(anyhow!("--to가 필요합니다 (또는 --from A...B 형식으로 범위를 지정하세요).")),
+            },
+            _ => Err(anyhow!("리비전 범위 분석을 위해서는 -f (from)와 -t (to) 또는 --since-tag가 필요합니다.")),
+        }
+    }
 }
 
-#[derive(Deserialize)]
-struct OpenAIResponse {
-    choices: Vec<Choice>,
+#[derive(clap::Args)]
+#[command(group = ArgGroup::new("target").args(["from", "since_tag", "staged", "worktree"]).required(true))]
+struct RangeArgs {
+    #[command(flatten)]
+    range: RevisionRange,
+    
+    /// 스테이징된 변경사항 분석 (git diff --cached)
+    #[arg(long, conflicts_with_all = ["from", "to", "since_tag", "merge_base", "worktree", "repo"])]
+    staged: bool,
+    
+    /// 작업 트리의 커밋되지 않은 모든 변경사항 분석 (git diff HEAD)
+    #[arg(long, conflicts_with_all = ["from", "to", "since_tag", "merge_base", "repo"])]
+    worktree: bool,
+    
+    #[command(flatten)]
+    analysis: AnalysisArgs,
 }
 
-#[derive(Deserialize)]
-struct Choice {
-    message: MessageResponse,
+impl RangeArgs {
+    /// 인자로 분석 대상 결정
+    fn source(&self) -> Result<DiffSource> {
+        match (self.staged, self.worktree) {
+            (true, _) => Ok(DiffSource::Staged),
+            (_, true) => Ok(DiffSource::Worktree),
+            _ => self.range.source(),
+        }
+    }
 }
 
-#[derive(Deserialize)]
-struct MessageResponse {
-    content: String,
+#[derive(clap::Args)]
+struct CommitArgs {
+    /// 분석할 커밋 (해시, 태그, HEAD~N 등)
+    commit: String,
+    
+    #[command(flatten)]
+    analysis: AnalysisArgs,
 }
 
-async fn get_git_diff(project_path: &str, from_tag: &str, to_tag: &str) -> Result<String> {
-    println!("{}에서 {} -> {} git diff 생성 중...", project_path, from_tag, to_tag);
-    
-    let output = Command::new("git")
-        .current_dir(project_path)
-        .args(&[
-            "diff", 
-            from_tag, 
-            to_tag,
-            "--",
-            ":!package-lock.json",      // npm lock file 제외
-            ":!yarn.lock",              // yarn lock file 제외
-            ":!pnpm-lock.yaml",         // pnpm lock file 제외
-            ":!composer.lock",          // composer lock file 제외
-            ":!Gemfile.lock",           // ruby lock file 제외
-            ":!poetry.lock",            // python poetry lock file 제외
-            ":!Pipfile.lock",           // python pipenv lock file 제외
-            ":!go.sum",                 // go modules checksum 제외
-            ":!*.min.js",               // 압축된 JS 파일 제외
-            ":!*.min.css",              // 압축된 CSS 파일 제외
-            ":!dist/*",                 // 빌드 결과물 제외
-            ":!build/*",                // 빌드 결과물 제외
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("Git diff 실행 실패: {}", stderr));
-    }
+#[derive(clap::Args)]
+#[command(group = ArgGroup::new("target").args(["from", "since_tag"]).required(true))]
+struct SeriesArgs {
+    #[command(flatten)]
+    range: RevisionRange,
+    
+    /// 동시에 분석할 커밋 수
+    #[arg(long, default_value_t = 4)]
+    concurrency: usize,
+    
+    #[command(flatten)]
+    analysis: AnalysisArgs,
+}
+
+/// 분석 하위 명령(range, commit, series)의 공통 옵션
+#[derive(clap::Args)]
+struct AnalysisArgs {
+    #[command(flatten)]
+    target: ProjectArgs,
+    
+    /// API를 호출하지 않고 프롬프트와 예상 토큰 수만 보고서 디렉토리에 저장 (API 키 불필요)
+    #[arg(long)]
+    dry_run: bool,
+    
+    /// 사이드 이펙트 리스크가 이 수준 이상이면 0이 아닌 코드로 종료하고 stdout 마지막 줄에 JSON 요약 출력 (CI용)
+    #[arg(long, value_enum, conflicts_with = "dry_run")]
+    fail_on: Option<FailOn>,
+    
+    /// 보고서를 저장할 디렉토리 (기본값: 설정 파일의 output_dir 또는 reports)
+    #[arg(long)]
+    output_dir: Option<PathBuf>,
+    
+    /// LLM 제공자 (기본값: 사용자 설정 파일의 provider 또는 openai)
+    #[arg(long, value_enum, env = "LLM_PROVIDER")]
+    provider: Option<ProviderKind>,
+    
+    /// 제공자 API 기본 URL (openai-compatible은 필수, 그 외에는 기본 엔드포인트 대체, 기본값: 사용자 설정 파일의 base_url)
+    #[arg(long, env = "LLM_BASE_URL")]
+    base_url: Option<String>,
+    
+    /// 분석에 사용할 모델 (기본값: 설정 파일의 [[models]] 또는 제공자별 기본 모델)
+    #[arg(long, env = "LLM_MODEL")]
+    model: Option<String>,
+    
+    /// 컨텍스트 초과, rate limit, 서비스 장애 시 재시도할 모델 (기본값: 설정 파일의 [[models]] 또는 제공자별 기본값)
+    #[arg(long, env = "LLM_FALLBACK_MODEL")]
+    fallback_model: Option<String>,
+    
+    /// rate limit(429), 서버 오류(5xx), 연결 실패 시 첫 요청을 포함한 최대 시도 횟수
+    #[arg(long, env = "LLM_MAX_ATTEMPTS", default_value_t = 4)]
+    max_attempts: u32,
+    
+    /// API 요청 하나의 제한 시간 (초, 응답 생성 시간 포함)
+    #[arg(long, env = "LLM_TIMEOUT", default_value_t = 300)]
+    timeout: u64,
+    
+    /// API 서버 연결 제한 시간 (초)
+    #[arg(long, default_value_t = 10)]
+    connect_timeout: u64,
+    
+    /// 큰 diff를 자르지 않고 청크별로 나누어 분석한 뒤 종합 (map-reduce)
+    #[arg(long)]
+    chunked: bool,
+    
+    /// 분석에서 제외할 파일 glob 패턴 (여러 번 지정 가능)
+    #[arg(long)]
+    exclude: Vec<String>,
+    
+    /// 지정하면 이 glob 패턴에 해당하는 파일만 분석 (여러 번 지정 가능)
+    #[arg(long)]
+    include: Vec<String>,
+    
+    /// 기본 제외 목록(lock 파일, 빌드 결과물 등)을 사용하지 않음
+    #[arg(long)]
+    no_default_excludes: bool,
+    
+    /// 분석 프로필 이름 또는 프로필 파일 경로 (기본값: web-media-m38)
+    #[arg(long)]
+    profile: Option<String>,
+    
+    /// 프로필 템플릿 변수 지정 (예: --var target_runtime="Chromium M49+", 여러 번 지정 가능)
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    vars: Vec<(String, String)>,
+    
+    /// 변경된 JS/TS 파일의 export를 변경 전후로 비교한 공개 API 변경 요약을 만들지 않음
+    #[arg(long)]
+    no_api_surface: bool,
+    
+    /// 정적 호환성 검사의 최소 지원 Chrome 버전 (기본값: 설정 파일 또는 프로필의 [compat] chrome)
+    #[arg(long)]
+    chrome_baseline: Option<u32>,
+    
+    /// 청크 하나의 최대 토큰 수 (기본값: 모델 컨텍스트에 맞춘 최대값)
+    #[arg(long, requires = "chunked")]
+    chunk_tokens: Option<usize>,
     
-    let diff_content = String::from_utf8_lossy(&output.stdout).to_string();
+    /// 응답을 스트리밍으로 받지 않고 완성된 뒤 한 번에 저장 (SSE를 지원하지 않는 서버용)
+    #[arg(long)]
+    no_stream: bool,
     
-    // 추가적으로 대용량 자동 생성 파일들을 필터링
-    let filtered_diff = filter_large_generated_files(&diff_content);
+    /// 최종 보고서를 JSON 스키마(구조화 출력)로 요청해 검증하고 _findings.json으로 함께 저장 (스트리밍 사용 안 함)
+    #[arg(long)]
+    structured: bool,
     
-    println!("Lock 파일 및 자동 생성 파일들이 제외된 diff가 생성되었습니다.");
+    /// 응답 캐시를 사용하지 않고 항상 API 호출 (새 응답도 저장하지 않음)
+    #[arg(long)]
+    no_cache: bool,
+}
+
+#[derive(clap::Args)]
+struct TagsArgs {
+    #[command(flatten)]
+    target: ProjectArgs,
     
-    Ok(filtered_diff)
+    /// 최신 태그부터 이 개수만 출력
+    #[arg(long)]
+    limit: Option<usize>,
 }
 
-async fn get_commit_diff(project_path: &str, commit_hash: &str) -> Result<String> {
-    println!("{}에서 커밋 {} 변경사항 분석 중...", project_path, commit_hash);
-    
-    let output = Command::new("git")
-        .current_dir(project_path)
-        .args(&[
-            "show", 
-            "--format=fuller",
-            commit_hash,
-            "--",
-            ":!package-lock.json",      // npm lock file 제외
-            ":!yarn.lock",              // yarn lock file 제외
-            ":!pnpm-lock.yaml",         // pnpm lock file 제외
-            ":!composer.lock",          // composer lock file 제외
-            ":!Gemfile.lock",           // ruby lock file 제외
-            ":!poetry.lock",            // python poetry lock file 제외
-            ":!Pipfile.lock",           // python pipenv lock file 제외
-            ":!go.sum",                 // go modules checksum 제외
-            ":!*.min.js",               // 압축된 JS 파일 제외
-            ":!*.min.css",              // 압축된 CSS 파일 제외
-            ":!dist/*",                 // 빌드 결과물 제외
-            ":!build/*",                // 빌드 결과물 제외
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("Git show 실행 실패: {}", stderr));
-    }
+#[derive(clap::Args)]
+struct PublishArgs {
+    /// 게시할 보고서 파일 (예: reports/my-project_v1.0.0_v1.1.0_summary.md)
+    report: PathBuf,
+    
+    /// 코드 호스팅 서비스 (토큰은 GITHUB_TOKEN 또는 GITLAB_TOKEN 환경변수에서 읽음)
+    #[arg(long, value_enum)]
+    platform: Platform,
     
-    let diff_content = String::from_utf8_lossy(&output.stdout).to_string();
+    /// GitHub은 OWNER/REPO, GitLab은 프로젝트 경로(group/project) 또는 ID
+    #[arg(long)]
+    repo: String,
     
-    // 추가적으로 대용량 자동 생성 파일들을 필터링
-    let filtered_diff = filter_large_generated_files(&diff_content);
+    /// PR 번호 또는 MR IID
+    #[arg(long)]
+    pr: u64,
     
-    println!("Lock 파일 및 자동 생성 파일들이 제외된 커밋 diff가 생성되었습니다.");
+    /// API 기본 URL (기본값: https://api.github.com 또는 https://gitlab.com/api/v4, GitHub Enterprise나 자체 GitLab용)
+    #[arg(long)]
+    api_url: Option<String>,
     
-    Ok(filtered_diff)
+    /// 수정할 댓글을 찾는 표시 (한 PR에 여러 보고서를 따로 게시할 때 다르게 지정)
+    #[arg(long, default_value = "report")]
+    marker: String,
 }
 
-fn filter_large_generated_files(diff_content: &str) -> String {
-    let lines: Vec<&str> = diff_content.lines().collect();
-    let mut filtered_lines = Vec::new();
-    let mut skip_file = false;
-    let mut current_file = String::new();
-    
-    for line in lines {
-        if line.starts_with("diff --git") {
-            // 새 파일 시작
-            skip_file = false;
-            if let Some(file_path) = line.split_whitespace().nth(3) {
-                current_file = file_path.trim_start_matches("b/").to_string();
-                
-                // 제외할 파일 패턴들
-                if should_skip_file(&current_file) {
-                    skip_file = true;
-                    continue;
-                }
-            }
-        }
-        
-        if !skip_file {
-            filtered_lines.push(line);
-        }
-    }
-    
-    filtered_lines.join("\n")
+#[derive(Subcommand)]
+enum CacheAction {
+    /// 캐시 위치, 항목 수, 크기 출력
+    Info,
+    /// 저장한 지 오래된 항목 삭제
+    Prune {
+        /// 이 일수보다 오래된 항목 삭제
+        #[arg(long, default_value_t = 30)]
+        older_than_days: u64,
+    },
+    /// 모든 항목 삭제
+    Clear,
 }
 
-fn should_skip_file(file_path: &str) -> bool {
-    let skip_patterns = [
-        // Lock files
-        "package-lock.json",
-        "yarn.lock", 
-        "pnpm-lock.yaml",
-        "composer.lock",
-        "Gemfile.lock",
-        "poetry.lock",
-        "Pipfile.lock",
-        "go.sum",
-        
-        // Generated/compiled files
-        ".min.js",
-        ".min.css",
-        ".bundle.js",
-        ".bundle.css",
-        
-        // Build directories
-        "dist/",
-        "build/",
-        "output/",
-        "out/",
-        
-        // Documentation auto-generated
-        "CHANGELOG.md",
-        
-        // IDE/Editor files
-        ".vscode/",
-        ".idea/",
-        
-        // OS files
-        ".DS_Store",
-        "Thumbs.db",
-        
-        // Large data files
-        ".json.map",
-        ".js.map",
-        ".css.map",
-    ];
-    
-    skip_patterns.iter().any(|pattern| {
-        file_path.contains(pattern) || file_path.ends_with(pattern)
-    })
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// 적용 순서대로의 설정 파일 경로 출력
+    Path(ConfigTarget),
+    /// 설정 파일들을 합친 최종 설정 출력
+    Show(ConfigTarget),
 }
 
-fn estimate_tokens(text: &str) -> usize {
-    // 대략적인 토큰 추정 (영어: 4글자≈1토큰, 한국어: 1글자≈1토큰)
-    let korean_chars = text.chars().filter(|c| *c >= '가' && *c <= '힣').count();
-    let other_chars = text.chars().count() - korean_chars;
+/// 저장소 설정 파일을 찾을 프로젝트 (지정하지 않으면 현재 디렉토리)
+#[derive(clap::Args)]
+struct ConfigTarget {
+    /// 프로젝트 이름
+    #[arg(short, long)]
+    project: Option<String>,
     
-    korean_chars + (other_chars / 4)
+    /// 프로젝트 경로 (선택사항, 기본값: ./repositories/{project})
+    #[arg(long)]
+    path: Option<String>,
 }
 
-fn smart_summarize_diff(diff_content: &str, max_tokens: usize) -> String {
-    let mut summary = String::new();
-    let lines: Vec<&str> = diff_content.lines().collect();
-    
-    // 기본 통계
-    let _total_lines = lines.len();
-    let added_lines = lines.iter().filter(|line| line.starts_with('+')).count();
-    let removed_lines = lines.iter().filter(|line| line.starts_with('-')).count();
-    let modified_files = lines.iter().filter(|line| line.starts_with("diff ")).count();
-    
-    summary.push_str(&format!("=== 통계 ===\n파일 {}개, +{} -{} 라인\n\n", modified_files, added_lines, removed_lines));
-    
-    // 전체 diff 내용을 토큰 제한에 맞춰 포함
-    let stats_tokens = estimate_tokens(&summary);
-    let available_tokens = max_tokens - stats_tokens;
-    
-    // diff 내용을 토큰 제한에 맞춰 자르기
-    let mut remaining_content = diff_content;
-    let mut truncated_content = String::new();
-    
-    for line in remaining_content.lines() {
-        let line_with_newline = format!("{}\n", line);
-        if estimate_tokens(&(truncated_content.clone() + &line_with_newline)) < available_tokens {
-            truncated_content.push_str(&line_with_newline);
-        } else {
-            truncated_content.push_str("... (토큰 제한으로 나머지 내용 생략)\n");
-            break;
+impl ConfigTarget {
+    fn project_path(&self) -> PathBuf {
+        match (&self.path, &self.project) {
+            (Some(path), _) => PathBuf::from(path),
+            (None, Some(project)) => PathBuf::from(format!("./repositories/{}", project)),
+            (None, None) => PathBuf::from("."),
         }
     }
-    
-    summary.push_str(&truncated_content);
-    summary
 }
 
-// 프롬프트 생성 헬퍼 함수들
-fn create_diff_analysis_prompt(project: &str, from_tag: &str, to_tag: &str, analysis_content: &str) -> String {
-    format!(
-        "{}의 {} → {} 변경사항을 라이브러리 사용자 관점에서 분석해주세요.
-
-{}
-
-{}
-
-{}
-
-다음 형식으로 마크다운 분석 보고서를 작성해주세요:
-
-# {} 변경사항 분석 ({} → {}) - 사이드 이펙트 분석
-
-## 📊 개요
-- 분석 대상: {} {} → {}
-- 분석 목적: 라이브러리 사용자의 사이드 이펙트 방지
-- 분석 기준: Chromium M38+ 버전 기준, 동작 변경 중심
-
-{}
-
-## ⚠️ 업데이트 시 주의사항
-
-실제 변경이 있는 파일들에 대해:
-- 반드시 테스트해야 할 시나리오
-- 업데이트 전 확인 사항
-- 단계별 적용 권장사항
-- **브라우저별 테스트 필수 목록** (호환성 문제 발견 시)
-
-{}
-
-**분석할 diff 데이터:**
-{}",
-        project, from_tag, to_tag,
-        ANALYSIS_PURPOSE,
-        ANALYSIS_CRITERIA,
-        API_COMPATIBILITY_CHECKLIST,
-        project, from_tag, to_tag,
-        project, from_tag, to_tag,
-        REPORT_FORMAT_SECTIONS,
-        REPORT_FORMAT_FOOTER,
-        analysis_content
-    )
+/// `KEY=VALUE` 형식의 인자 파싱
+fn parse_key_value(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("KEY=VALUE 형식이 아닙니다: {}", value))
 }
 
-fn create_commit_analysis_prompt(project: &str, commit_hash: &str, analysis_content: &str) -> String {
-    format!(
-        "{}의 커밋 {} 변경사항을 라이브러리 사용자 관점에서 분석해주세요.
-
-{}
-
-{}
-
-{}
-
-다음 형식으로 마크다운 분석 보고서를 작성해주세요:
-
-# {} 커밋 {} 변경사항 분석 - 사이드 이펙트 분석
-
-## 📊 개요
-- 분석 대상: {} 커밋 {}
-- 분석 목적: 라이브러리 사용자의 사이드 이펙트 방지
-- 분석 기준: Chromium M38+ 버전 기준, 동작 변경 중심
-
-{}
-
-## ⚠️ 커밋 적용 시 주의사항
+/// 분석 모델 체인 결정 (CLI 인자/환경변수 > 설정 파일의 [[models]] > 제공자 기본값)
+fn model_chain(args: &AnalysisArgs, provider: ProviderKind, config: &FileConfig) -> Result<Vec<ModelConfig>> {
+    if args.model.is_none() && args.fallback_model.is_none() && !config.models.is_empty() {
+        return Ok(config.models.clone());
+    }
+    
+    let (default_model, default_fallback) = provider.default_models();
+    let primary = args.model.clone()
+        .or_else(|| default_model.map(str::to_string))
+        .ok_or_else(|| anyhow!("선택한 제공자에는 기본 모델이 없으므로 --model 지정이 필요합니다."))?;
+    let mut models = vec![ModelConfig::new(primary)];
+    
+    // 대체 모델은 작은 요약과 짧은 응답으로 분석
+    if let Some(fallback) = args.fallback_model.clone().or_else(|| default_fallback.map(str::to_string)) {
+        models.push(ModelConfig {
+            max_output_tokens: 2000,
+            budget: Some(6000),
+            ..ModelConfig::new(fallback)
+        });
+    }
+    Ok(models)
+}
 
-실제 변경이 있는 파일들에 대해:
-- 반드시 테스트해야 할 시나리오
-- 커밋 적용 전 확인 사항
-- 단계별 적용 권장사항
+/// 스트리밍으로 작성하던 요약 파일 끝에 보고서가 완성되지 않았음을 표시
+fn mark_incomplete(summary_path: &Path, reason: &str) -> Result<()> {
+    let mut file = OpenOptions::new().append(true).open(summary_path)?;
+    writeln!(file, "\n\n> ⚠️ {} 보고서가 완성되지 않았습니다.", reason)?;
+    Ok(())
+}
 
-{}
+/// `--fail-on` 판정 결과를 출력하고, 기준을 넘었거나 판단할 수 없으면 해당 종료 코드로 종료
+fn finish_gate(result: Option<GateResult>) -> Result<()> {
+    let Some(result) = result else {
+        return Ok(());
+    };
+    result.print();
+    match result.exit_code() {
+        0 => Ok(()),
+        code => std::process::exit(code),
+    }
+}
 
-**분석할 커밋 데이터:**
-{}",
-        project, commit_hash,
-        ANALYSIS_PURPOSE,
-        ANALYSIS_CRITERIA,
-        API_COMPATIBILITY_CHECKLIST,
-        project, commit_hash,
-        project, commit_hash,
-        REPORT_FORMAT_SECTIONS,
-        REPORT_FORMAT_FOOTER,
-        analysis_content
-    )
+/// dry-run으로 기록한 프롬프트 저장
+fn save_dry_run_prompts(records: &[RecordedPrompt], writer: &ReportWriter) -> Result<()> {
+    let (prompt_dir, report_path) = writer.write_dry_run_prompts(records)?;
+    println!("프롬프트 {}개가 {}에 저장되었습니다.", records.len(), prompt_dir.display());
+    println!("예상 토큰 요약: {}", report_path.display());
+    Ok(())
 }
 
-async fn analyze_diff_with_openai(diff_content: &str, api_key: &str, project: &str, from_tag: &str, to_tag: &str) -> Result<String> {
-    println!("OpenAI API로 diff 분석 중...");
-    
-    let client = Client::new();
-    
-    // 프롬프트 토큰 추정 (약 800 토큰)
-    let prompt_base_tokens = 800;
-    let max_content_tokens = 120000 - prompt_base_tokens - 4000; // GPT-4 Turbo: 128k, 응답용 4k 예약
-    
-    // diff 내용 처리
-    let analysis_content = if estimate_tokens(diff_content) > max_content_tokens {
-        println!("Diff 내용이 큽니다. 스마트 요약해서 분석합니다...");
-        smart_summarize_diff(diff_content, max_content_tokens)
-    } else {
-        diff_content.to_string()
+/// 프로젝트 저장소를 엶 (원격 저장소는 캐시된 clone을 최신 상태로 갱신)
+async fn open_project(target: &ProjectArgs, cli: &Cli) -> Result<(String, Box<dyn RepoBackend>)> {
+    // blob 없는 clone은 필요한 파일 내용을 git이 받아와야 하므로 git 명령 사용
+    let git_backend = match (cli.git_backend, target.repo.is_some()) {
+        (Some(BackendKind::Libgit2), true) => {
+            return Err(anyhow!("--repo로 받은 partial clone은 libgit2로 읽을 수 없습니다. --git-backend cli를 사용해주세요."));
+        }
+        (Some(kind), _) => kind,
+        (None, true) => BackendKind::Cli,
+        (None, false) => BackendKind::Libgit2,
     };
     
-    println!("예상 토큰 사용량: {} / 128,000", estimate_tokens(&analysis_content) + prompt_base_tokens);
-    
-    let prompt = create_diff_analysis_prompt(project, from_tag, to_tag, &analysis_content);
-    
-    // 먼저 GPT-4 Turbo 시도
-    let mut request = OpenAIRequest {
-        model: "gpt-4-turbo".to_string(),
-        messages: vec![
-            Message {
-                role: "user".to_string(),
-                content: prompt.clone(),
-            }
-        ],
-        max_tokens: 4000,
-        temperature: 0.3,
+    let project_path = match &target.repo {
+        Some(url) => {
+            let dir = match cli.repo_cache_dir.clone() {
+                Some(dir) => dir,
+                None => RepoCache::default_dir()?,
+            };
+            RepoCache::new(dir).sync(url).await?.to_string_lossy().into_owned()
+        }
+        None => target.path.clone().unwrap_or_else(|| {
+            format!("./repositories/{}", target.project)
+        }),
     };
     
-    let response = client
-        .post("https://api.openai.com/v1/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await?;
+    // 프로젝트 경로 존재 확인
+    if !Path::new(&project_path).exists() {
+        return Err(anyhow!("프로젝트 경로가 존재하지 않습니다: {} (원격 저장소는 --repo <URL>로 지정할 수 있습니다)", project_path));
+    }
     
-    if response.status().is_success() {
-        let openai_response: OpenAIResponse = response.json().await?;
-        
-        if !openai_response.choices.is_empty() {
-            return Ok(openai_response.choices[0].message.content.clone());
-        }
+    let repo = open_backend(git_backend, Path::new(&project_path))?;
+    Ok((project_path, repo))
+}
+
+/// 태그 접두사 (CLI 인자 > 설정 파일의 [tags] prefixes)
+fn tag_prefixes(cli: &[String], config: &TagsConfig) -> Vec<String> {
+    match cli.is_empty() {
+        true => config.prefixes.clone(),
+        false => cli.to_vec(),
+    }
+}
+
+async fn run_tags_command(tags_args: &TagsArgs, cli: &Cli) -> Result<()> {
+    let (project_path, repo) = open_project(&tags_args.target, cli).await?;
+    let config = load_config(&config_layers(cli.config.as_deref(), Path::new(&project_path)))?;
+    let index = TagIndex::load(&*repo, &tag_prefixes(&cli.tag_prefix, &config.tags)).await?;
+    
+    let releases = index.releases();
+    if releases.is_empty() {
+        println!("semver 버전으로 해석할 수 있는 태그가 없습니다. (태그 접두사: {})", index.prefixes_label());
     } else {
-        let error_text = response.text().await?;
+        let latest = index.latest().map(|release| release.tag.name.as_str());
+        let previous = latest.and_then(|latest| index.previous(latest)).map(|release| release.tag.name.as_str());
+        println!("릴리스 태그 {}개 (semver 역순, 태그 접두사: {})", releases.len(), index.prefixes_label());
         
-        // 토큰 제한 오류인 경우 GPT-3.5 Turbo로 fallback
-        if error_text.contains("context_length_exceeded") || error_text.contains("maximum context length") {
-            println!("GPT-4 Turbo 토큰 제한에 걸렸습니다. GPT-3.5 Turbo로 재시도합니다...");
-            
-            // 더 작은 요약으로 재시도
-            let fallback_content = if estimate_tokens(&analysis_content) > 8000 {
-                smart_summarize_diff(&analysis_content, 6000)
-            } else {
-                analysis_content
-            };
-            
-            let fallback_prompt = create_diff_analysis_prompt(project, from_tag, to_tag, &fallback_content);
-            
-            request.model = "gpt-3.5-turbo".to_string();
-            request.messages[0].content = fallback_prompt;
-            request.max_tokens = 2000;
-            
-            let fallback_response = client
-                .post("https://api.openai.com/v1/chat/completions")
-                .header("Authorization", format!("Bearer {}", api_key))
-                .header("Content-Type", "application/json")
-                .json(&request)
-                .send()
-                .await?;
-            
-            if fallback_response.status().is_success() {
-                let fallback_result: OpenAIResponse = fallback_response.json().await?;
-                
-                if !fallback_result.choices.is_empty() {
-                    println!("GPT-3.5 Turbo로 분석 완료!");
-                    return Ok(fallback_result.choices[0].message.content.clone());
-                }
+        let shown = tags_args.limit.unwrap_or(releases.len()).min(releases.len());
+        let width = releases.iter().map(|release| release.tag.name.chars().count()).max().unwrap_or(0);
+        for release in releases.iter().rev().take(shown) {
+            let name = release.tag.name.as_str();
+            let mut marks = Vec::new();
+            if Some(name) == latest {
+                marks.push("latest");
+            }
+            if Some(name) == previous {
+                marks.push("previous");
             }
+            if release.is_prerelease() {
+                marks.push("사전 릴리스");
+            }
+            let marks = match marks.is_empty() {
+                true => String::new(),
+                false => format!("  ({})", marks.join(", ")),
+            };
+            println!("  {:<width$}  {}  {}{}", name, release.tag.date, release.tag.short_sha(), marks, width = width);
+        }
+        if shown < releases.len() {
+            println!("  ... 이전 태그 {}개 생략 (--limit으로 조정)", releases.len() - shown);
         }
-        
-        return Err(anyhow!("OpenAI API 요청 실패: {}", error_text));
     }
-    
-    Err(anyhow!("OpenAI API에서 응답을 받지 못했습니다"))
+    if index.unversioned() > 0 {
+        println!("semver 버전으로 해석할 수 없는 태그 {}개는 제외했습니다.", index.unversioned());
+    }
+    Ok(())
 }
 
-async fn analyze_commit_with_openai(diff_content: &str, api_key: &str, project: &str, commit_hash: &str) -> Result<String> {
-    println!("OpenAI API로 커밋 분석 중...");
-    
-    let client = Client::new();
-    
-    // 프롬프트 토큰 추정 (약 800 토큰)
-    let prompt_base_tokens = 800;
-    let max_content_tokens = 120000 - prompt_base_tokens - 4000; // GPT-4 Turbo: 128k, 응답용 4k 예약
-    
-    // diff 내용 처리
-    let analysis_content = if estimate_tokens(diff_content) > max_content_tokens {
-        println!("Diff 내용이 큽니다. 스마트 요약해서 분석합니다...");
-        smart_summarize_diff(diff_content, max_content_tokens)
-    } else {
-        diff_content.to_string()
+fn open_cache(dir: Option<PathBuf>) -> Result<ResponseCache> {
+    let dir = match dir {
+        Some(dir) => dir,
+        None => ResponseCache::default_dir()?,
     };
-    
-    println!("예상 토큰 사용량: {} / 128,000", estimate_tokens(&analysis_content) + prompt_base_tokens);
-    
-    let prompt = create_commit_analysis_prompt(project, commit_hash, &analysis_content);
-    
-    // 먼저 GPT-4 Turbo 시도
-    let mut request = OpenAIRequest {
-        model: "gpt-4-turbo".to_string(),
-        messages: vec![
-            Message {
-                role: "user".to_string(),
-                content: prompt.clone(),
+    Ok(ResponseCache::new(dir))
+}
+
+/// `cache` 하위 명령 실행
+fn run_cache_command(action: &CacheAction, cache: &ResponseCache) -> Result<()> {
+    match action {
+        CacheAction::Info => {
+            println!("캐시 디렉토리: {}", cache.dir().display());
+            println!("캐시 항목: {}", cache.stats()?);
+        }
+        CacheAction::Prune { older_than_days } => {
+            let removed = cache.prune(Duration::from_secs(older_than_days * 24 * 60 * 60))?;
+            println!("{}일보다 오래된 캐시 항목 {}를 삭제했습니다.", older_than_days, removed);
+            println!("남은 캐시 항목: {}", cache.stats()?);
+        }
+        CacheAction::Clear => {
+            let removed = cache.clear()?;
+            println!("캐시 항목 {}를 삭제했습니다. ({})", removed, cache.dir().display());
+        }
+    }
+    Ok(())
+}
+
+/// `config` 하위 명령 실행
+fn run_config_command(action: &ConfigAction, cli: &Cli) -> Result<()> {
+    let (ConfigAction::Path(target) | ConfigAction::Show(target)) = action;
+    let project_path = target.project_path();
+    match action {
+        ConfigAction::Path(_) => {
+            match user_config_file() {
+                Some(path) if path.is_file() => println!("사용자 설정: {}", path.display()),
+                Some(path) => println!("사용자 설정: {} (없음)", path.display()),
+                None => println!("사용자 설정: 사용자 설정 디렉토리를 찾을 수 없습니다."),
+            }
+            match find_config_file(cli.config.as_deref(), &project_path) {
+                Some(path) => println!("저장소 설정: {}", path.display()),
+                None => println!("저장소 설정: 없음 (프로젝트 루트와 현재 디렉토리에 {} 파일이 없습니다)", CONFIG_FILE_NAME),
             }
-        ],
-        max_tokens: 4000,
-        temperature: 0.3,
-    };
-    
-    let response = client
-        .post("https://api.openai.com/v1/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await?;
-    
-    if response.status().is_success() {
-        let openai_response: OpenAIResponse = response.json().await?;
-        
-        if !openai_response.choices.is_empty() {
-            return Ok(openai_response.choices[0].message.content.clone());
         }
-    } else {
-        let error_text = response.text().await?;
-        
-        // 토큰 제한 오류인 경우 GPT-3.5 Turbo로 fallback
-        if error_text.contains("context_length_exceeded") || error_text.contains("maximum context length") {
-            println!("GPT-4 Turbo 토큰 제한에 걸렸습니다. GPT-3.5 Turbo로 재시도합니다...");
-            
-            // 더 작은 요약으로 재시도
-            let fallback_content = if estimate_tokens(&analysis_content) > 8000 {
-                smart_summarize_diff(&analysis_content, 6000)
+        ConfigAction::Show(_) => {
+            let merged = merge_layers(&config_layers(cli.config.as_deref(), &project_path))?;
+            if merged.is_empty() {
+                println!("적용되는 설정이 없습니다. (기본값 사용)");
             } else {
-                analysis_content
-            };
-            
-            let fallback_prompt = create_commit_analysis_prompt(project, commit_hash, &fallback_content);
-            
-            request.model = "gpt-3.5-turbo".to_string();
-            request.messages[0].content = fallback_prompt;
-            request.max_tokens = 2000;
-            
-            let fallback_response = client
-                .post("https://api.openai.com/v1/chat/completions")
-                .header("Authorization", format!("Bearer {}", api_key))
-                .header("Content-Type", "application/json")
-                .json(&request)
-                .send()
-                .await?;
-            
-            if fallback_response.status().is_success() {
-                let fallback_result: OpenAIResponse = fallback_response.json().await?;
-                
-                if !fallback_result.choices.is_empty() {
-                    println!("GPT-3.5 Turbo로 분석 완료!");
-                    return Ok(fallback_result.choices[0].message.content.clone());
-                }
+                println!();
+                print!("{}", toml::to_string(&merged)?);
             }
         }
-        
-        return Err(anyhow!("OpenAI API 요청 실패: {}", error_text));
     }
-    
-    Err(anyhow!("OpenAI API에서 응답을 받지 못했습니다"))
+    Ok(())
 }
 
-fn save_diff_to_file(diff_content: &str, filename: &str) -> Result<()> {
-    fs::write(filename, diff_content)?;
-    println!("Git diff가 {}에 저장되었습니다.", filename);
+async fn run_publish_command(args: &PublishArgs) -> Result<()> {
+    let report = fs::read_to_string(&args.report)
+        .map_err(|error| anyhow!("보고서 파일을 읽을 수 없습니다: {} ({})", args.report.display(), error))?;
+    let publisher = CommentPublisher::new(args.platform, args.api_url.clone(), args.repo.clone(), args.pr)?;
+    
+    println!("{} #{}에 보고서를 게시하는 중... ({})", args.repo, args.pr, args.report.display());
+    match publisher.publish(&report, &args.marker).await? {
+        PublishOutcome::Created(id) => println!("새 댓글을 작성했습니다. (댓글 ID {})", id),
+        PublishOutcome::Updated(id) => println!("기존 댓글을 수정했습니다. (댓글 ID {})", id),
+    }
     Ok(())
 }
 
-fn save_summary_to_file(summary: &str, filename: &str) -> Result<()> {
-    fs::write(filename, summary)?;
-    println!("분석 요약이 {}에 저장되었습니다.", filename);
-    Ok(())
+/// 라이브러리의 진행 상황 로그를 그대로 출력하는 로거
+struct ProgressLogger;
+
+impl log::Log for ProgressLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        // 의존 크레이트(reqwest 등)의 로그는 출력하지 않음
+        metadata.target().starts_with("git_diff_analyzer")
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            println!("{}", record.args());
+        }
+    }
+
+    fn flush(&self) {}
 }
 
+static LOGGER: ProgressLogger = ProgressLogger;
+
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenv().ok();
-    
-    let args = Args::parse();
-    
-    // 인자 유효성 검증
-    if args.commit.is_some() && (args.from_tag.is_some() || args.to_tag.is_some()) {
-        return Err(anyhow!("커밋 분석(-c)과 태그 간 분석(-f, -t)을 동시에 사용할 수 없습니다."));
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(log::LevelFilter::Info);
     }
     
-    if args.commit.is_none() && (args.from_tag.is_none() || args.to_tag.is_none()) {
-        return Err(anyhow!("태그 간 분석을 위해서는 -f (from_tag)와 -t (to_tag) 모두 필요하거나, 커밋 분석을 위해서는 -c (commit)이 필요합니다."));
+    let cli = Cli::parse();
+    match &cli.command {
+        Command::Range(range_args) => run_gated(&cli, &range_args.analysis, range_args.source(), None).await,
+        Command::Commit(commit_args) => {
+            run_gated(&cli, &commit_args.analysis, Ok(DiffSource::Commit(commit_args.commit.clone())), None).await
+        }
+        Command::Series(series_args) => {
+            run_gated(&cli, &series_args.analysis, series_args.range.source(), Some(series_args.concurrency)).await
+        }
+        Command::Tags(tags_args) => run_tags_command(tags_args, &cli).await,
+        Command::Cache { action } => run_cache_command(action, &open_cache(cli.cache_dir.clone())?),
+        Command::Config { action } => run_config_command(action, &cli),
+        Command::Publish(publish_args) => run_publish_command(publish_args).await,
     }
-    
-    // OpenAI API 키 확인
-    let api_key = env::var("OPENAI_API_KEY")
-        .map_err(|_| anyhow!("OPENAI_API_KEY 환경변수가 설정되지 않았습니다. .env 파일을 확인해주세요."))?;
-    
-    if api_key == "your_openai_api_key_here" {
-        return Err(anyhow!("OPENAI_API_KEY를 실제 API 키로 변경해주세요."));
+}
+
+/// 분석 하위 명령 실행
+///
+/// `--fail-on`을 지정했으면 실행 오류도 판정 결과 JSON으로 출력해 CI가 리스크 차단과 도구 오류를 구분할 수 있게 함
+async fn run_gated(cli: &Cli, args: &AnalysisArgs, source: Result<DiffSource>, concurrency: Option<usize>) -> Result<()> {
+    let target = source.as_ref().map(DiffSource::description).unwrap_or_default();
+    let result = match source {
+        Ok(source) => run_analysis(cli, args, source, concurrency).await,
+        Err(error) => Err(error),
+    };
+    if let (Err(error), Some(fail_on)) = (&result, args.fail_on) {
+        GateResult::error(fail_on, target, error).print();
     }
+    result
+}
+
+/// 분석 실행 (`concurrency`를 지정하면 범위의 커밋을 하나씩 분석)
+async fn run_analysis(cli: &Cli, args: &AnalysisArgs, source: DiffSource, concurrency: Option<usize>) -> Result<()> {
+    let project = args.target.project.clone();
+    let (project_path, repo) = open_project(&args.target, cli).await?;
+    
+    // 설정 파일 (사용자 설정 < 저장소 설정 < CLI 인자)
+    let mut config = load_config(&config_layers(cli.config.as_deref(), Path::new(&project_path)))?;
+    
+    // LLM 제공자 및 모델 선택 (dry-run은 요청을 기록만 함)
+    let provider_kind = args.provider.or(config.provider).unwrap_or(ProviderKind::Openai);
+    let mut dry_run_records = None;
+    let provider: Box<dyn LlmProvider> = if args.dry_run {
+        let (provider, records) = DryRunProvider::new();
+        dry_run_records = Some(records);
+        Box::new(provider)
+    } else {
+        let options = RequestOptions {
+            timeout: Duration::from_secs(args.timeout),
+            connect_timeout: Duration::from_secs(args.connect_timeout),
+            max_attempts: args.max_attempts,
+        };
+        create_provider(provider_kind, args.base_url.clone().or_else(|| config.base_url.clone()), &options)?
+    };
+    let models = model_chain(args, provider_kind, &config)?;
     
-    // 프로젝트 경로 설정
-    let project_path = args.path.unwrap_or_else(|| {
-        format!("./repositories/{}", args.project)
-    });
-    
-    // 프로젝트 경로 존재 확인
-    if !Path::new(&project_path).exists() {
-        return Err(anyhow!("프로젝트 경로가 존재하지 않습니다: {}", project_path));
+    // 파일 제외 규칙 (설정 파일 + CLI 인자)
+    if args.no_default_excludes {
+        config.filter.default_excludes = false;
     }
-    
-    // reports 디렉토리 생성
-    fs::create_dir_all("reports")?;
-    
-    println!("프로젝트: {}", args.project);
+    let rules = DiffFilter::new(&config.filter, &args.exclude, &args.include)?;
+    let source = resolve_tag_shortcuts(&*repo, source, &tag_prefixes(&cli.tag_prefix, &config.tags)).await?;
+    
+    // 분석 프로필 (CLI > 설정 파일 > 기본 프로필)
+    let profile_name = args.profile.clone()
+        .or_else(|| config.profile.clone())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    let profile = Profile::load(&profile_name, Path::new(&project_path))?;
+    let chrome_baseline = args.chrome_baseline
+        .or(config.compat.chrome)
+        .or(profile.chrome_baseline);
+    let (from_ref, to_ref) = source.refs();
+    let refs = source.description();
+    let mut variables = config.variables.clone();
+    variables.extend(args.vars.iter().cloned());
+    let mut profile = profile.render(
+        &[("project", &project), ("from_ref", &from_ref), ("to_ref", &to_ref), ("refs", &refs)],
+        &variables,
+    )?;
+    profile.relevance_keywords.extend(config.relevance.keywords.iter().cloned());
+    
+    // 보고서 디렉토리 (CLI > 설정 파일 > reports)
+    let output_dir = args.output_dir.clone()
+        .or_else(|| config.output_dir.clone())
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_OUTPUT_DIR));
+    
+    println!("프로젝트: {}", project);
     println!("프로젝트 경로: {}", project_path);
-    
-    let (diff_content, diff_filename, summary_filename, analysis_title, from_ref, to_ref) = if let Some(commit) = &args.commit {
-        // 커밋 분석 모드
-        println!("커밋: {}", commit);
-        
-        let diff_filename = format!("reports/{}_commit_{}_diff.txt", args.project, commit);
-        let summary_filename = format!("reports/{}_commit_{}_summary.md", args.project, commit);
-        
-        let diff_content = get_commit_diff(&project_path, commit).await?;
-        let analysis_title = format!("{} 커밋 {} 변경사항 분석", args.project, commit);
-        
-        (diff_content, diff_filename, summary_filename, analysis_title, commit.clone(), "".to_string())
-    } else {
-        // 태그 간 분석 모드
-        let from_tag = args.from_tag.as_ref().unwrap();
-        let to_tag = args.to_tag.as_ref().unwrap();
+    println!("분석 프로필: {} ({})", profile.name, profile.description);
+    println!("분석 모델: {}", models.iter().map(|model| model.name.as_str()).collect::<Vec<_>>().join(" → "));
+    
+    println!("분석 대상: {}", source.description());
+    
+    verify_revisions(&*repo, &source).await?;
+    let writer = ReportWriter::new(&output_dir, &project, &source)?;
+    
+    let compat_scanner = chrome_baseline.map(CompatScanner::new).transpose()?;
+    let chunk_tokens = args.chunked.then(|| args.chunk_tokens.unwrap_or(usize::MAX));
+    // dry-run은 실제 응답이 없으므로 캐시를 읽거나 쓰지 않음
+    let cache = (!args.no_cache && !args.dry_run).then(|| open_cache(cli.cache_dir.clone())).transpose()?;
+    let analyzer = Analyzer::new(provider, models, profile, chunk_tokens)?
+        .with_cache(cache)
+        .with_structured_output(args.structured);
+    
+    // 커밋 시리즈 모드: 범위의 커밋을 하나씩 분석
+    if let Some(concurrency) = concurrency {
+        let DiffSource::Range { from, to, .. } = &source else {
+            unreachable!("series는 리비전 범위만 받음");
+        };
+        let commits = repo.list_commits(from, to).await?;
+        if commits.is_empty() {
+            println!("{}", source.empty_message());
+            return finish_gate(args.fail_on.map(|fail_on| GateResult::no_changes(fail_on, source.description())));
+        }
+        println!("커밋 {}개를 최대 {}개씩 동시에 분석합니다...", commits.len(), concurrency);
         
-        println!("이전 태그: {}", from_tag);
-        println!("이후 태그: {}", to_tag);
+        let output_dir = writer.commits_dir();
+        let runner = SeriesRunner {
+            project: &project,
+            repo: &*repo,
+            rules: &rules,
+            analyzer: &analyzer,
+            compat: compat_scanner.as_ref(),
+            api_surface: !args.no_api_surface,
+            output_dir: &output_dir,
+        };
+        let entries = runner.run(commits, concurrency).await?;
         
-        let diff_filename = format!("reports/{}_{}_{}_diff.txt", args.project, from_tag, to_tag);
-        let summary_filename = format!("reports/{}_{}_{}_summary.md", args.project, from_tag, to_tag);
+        let output_dir_name = output_dir.file_name().unwrap_or_default().to_string_lossy();
+        let index = format_index(&project, &source.description(), &output_dir_name, &entries);
+        let index_path = writer.write_index(&index)?;
         
-        let diff_content = get_git_diff(&project_path, from_tag, to_tag).await?;
-        let analysis_title = format!("{} 변경사항 분석 ({} → {})", args.project, from_tag, to_tag);
+        if let Some(records) = &dry_run_records {
+            save_dry_run_prompts(&records.lock().unwrap(), &writer)?;
+        }
         
-        (diff_content, diff_filename, summary_filename, analysis_title, from_tag.clone(), to_tag.clone())
-    };
+        println!("\n분석 완료!");
+        println!("커밋별 보고서: {}", output_dir.display());
+        println!("인덱스 파일: {}", index_path.display());
+        let gate = args.fail_on.map(|fail_on| {
+            GateResult::for_series(fail_on, source.description(), &entries, index_path.display().to_string())
+        });
+        return finish_gate(gate);
+    }
+    
+    let (diff, excluded) = get_diff(&*repo, &source, &rules).await?;
     
-    if diff_content.trim().is_empty() {
-        if args.commit.is_some() {
-            println!("해당 커밋에 변경사항이 없습니다.");
-        } else {
-            println!("두 태그 간에 변경사항이 없습니다.");
+    let summary_path = writer.summary_path();
+    
+    // 제외된 파일과 사유 보고
+    if !excluded.is_empty() {
+        println!("분석에서 제외된 파일 {}개:", excluded.len());
+        for file in &excluded {
+            println!("  - {}: {}", file.path, file.reason);
         }
-        return Ok(());
+        let excluded_path = writer.write_excluded(&excluded)?;
+        println!("제외된 파일 목록이 {}에 저장되었습니다.", excluded_path.display());
+    }
+    
+    if diff.is_empty() {
+        println!("{}", source.empty_message());
+        return finish_gate(args.fail_on.map(|fail_on| GateResult::no_changes(fail_on, source.description())));
     }
     
     // Diff를 파일로 저장
-    save_diff_to_file(&diff_content, &diff_filename)?;
+    let diff_path = writer.write_diff(&diff)?;
+    println!("Git diff가 {}에 저장되었습니다.", diff_path.display());
+    
+    // 추가된 JS/TS 코드의 브라우저 호환성 정적 검사
+    let compat_report = compat_scanner.as_ref().map(|scanner| scanner.scan(&diff));
+    if let Some(report) = &compat_report {
+        println!("정적 호환성 검사 (Chrome {} 기준): {}건 발견", report.baseline, report.findings.len());
+        for group in report.groups() {
+            let locations: Vec<String> = group
+                .listed()
+                .iter()
+                .map(|finding| format!("{}:{}", finding.path, finding.line.unwrap_or_default()))
+                .collect();
+            let omitted = match group.omitted() {
+                0 => String::new(),
+                omitted => format!(" 외 {}건", omitted),
+            };
+            println!("  - {}: {}건 ({}{})", group.description(), group.findings.len(), locations.join(", "), omitted);
+        }
+    }
     
-    // OpenAI API로 분석 (개선된 프롬프트)
-    let summary = if args.commit.is_some() {
-        analyze_commit_with_openai(&diff_content, &api_key, &args.project, &from_ref).await?
-    } else {
-        analyze_diff_with_openai(&diff_content, &api_key, &args.project, &from_ref, &to_ref).await?
+    // 변경된 JS/TS 파일의 내보낸 심볼 비교 (원본 diff보다 앞에 프롬프트에 포함)
+    let api_delta = match args.no_api_surface {
+        true => None,
+        false => api_surface::compare(&*repo, &source, &diff).await?,
+    };
+    if let Some(delta) = &api_delta {
+        println!(
+            "공개 API 변경 (JS/TS 파일 {}개): 추가 {}개, 삭제 {}개, 변경 {}개",
+            delta.files,
+            delta.count(ChangeKind::Added),
+            delta.count(ChangeKind::Removed),
+            delta.count(ChangeKind::Changed)
+        );
+    }
+    let static_sections: Vec<String> = [
+        api_delta.as_ref().map(|delta| delta.prompt_section()),
+        compat_report.as_ref().map(|report| report.prompt_section()),
+    ].into_iter().flatten().collect();
+    let static_findings = (!static_sections.is_empty()).then(|| static_sections.join("\n\n"));
+    
+    // 선택한 LLM 제공자로 분석 (스트리밍이면 받는 대로 터미널과 요약 파일에 기록)
+    // 구조화 출력은 JSON 응답을 검증한 뒤 보고서를 작성하므로 스트리밍하지 않음
+    let streaming = !args.no_stream && !args.dry_run && !args.structured;
+    let mut summary_file = match streaming {
+        true => Some(fs::File::create(&summary_path)?),
+        false => None,
+    };
+    let mut on_delta = |delta: &str| {
+        print!("{}", delta);
+        io::stdout().flush().ok();
+        // 최종 요약은 분석이 끝난 뒤 다시 저장하므로 여기서의 쓰기 실패는 무시
+        if let Some(file) = summary_file.as_mut() {
+            file.write_all(delta.as_bytes()).and_then(|_| file.flush()).ok();
+        }
+    };
+    let stream = streaming.then_some(&mut on_delta as _);
+    let analysis = analyzer.analyze(source.target(&project), &diff, static_findings.as_deref(), stream);
+    let analysis = tokio::select! {
+        result = analysis => match result {
+            Ok(analysis) => analysis,
+            // 스트리밍 중 실패하면 받은 응답 일부는 요약 파일에 남기고 미완성임을 표시
+            Err(error) if streaming && fs::metadata(&summary_path)?.len() > 0 => {
+                mark_incomplete(&summary_path, "분석이 실패해")?;
+                return Err(error.context(format!("지금까지 받은 응답: {}", summary_path.display())));
+            }
+            Err(error) => return Err(error),
+        },
+        _ = tokio::signal::ctrl_c() => {
+            // 지금까지 받은 응답은 요약 파일에 남기고 미완성임을 표시
+            println!();
+            if streaming {
+                mark_incomplete(&summary_path, "분석이 중단되어")?;
+            }
+            return Err(anyhow!("분석이 중단되었습니다. 지금까지 받은 응답: {}", summary_path.display()));
+        }
+    };
+    if streaming {
+        println!();
+    }
+    let risk = match &analysis.findings {
+        Some(findings) => findings.overall_risk,
+        None => Risk::from_report(&analysis.markdown),
     };
     
+    let stats = DiffStats::new(&diff);
+    let summary = compose_summary(&analysis, &stats, api_delta.as_ref(), compat_report.as_ref());
+    
+    // dry-run은 응답이 없으므로 요약 대신 프롬프트 저장
+    if let Some(records) = &dry_run_records {
+        save_dry_run_prompts(&records.lock().unwrap(), &writer)?;
+        println!("\nDry-run 완료! (API를 호출하지 않았습니다)");
+        return Ok(());
+    }
+    
     // 요약을 마크다운 파일로 저장
-    save_summary_to_file(&summary, &summary_filename)?;
+    writer.write_summary(&summary)?;
+    println!("분석 요약이 {}에 저장되었습니다.", summary_path.display());
+    let stats_path = writer.write_stats(&stats)?;
+    let findings_path = analysis.findings.as_ref().map(|findings| writer.write_findings(findings)).transpose()?;
+    if let Some(path) = &findings_path {
+        println!("발견 사항이 {}에 저장되었습니다.", path.display());
+    }
     
     println!("\n분석 완료!");
-    println!("Git diff 파일: {}", diff_filename);
-    println!("요약 파일: {}", summary_filename);
+    println!("Git diff 파일: {}", diff_path.display());
+    println!("요약 파일: {}", summary_path.display());
+    println!("변경 통계 파일: {}", stats_path.display());
+    if let Some(path) = &findings_path {
+        println!("발견 사항 파일: {}", path.display());
+    }
     
-    Ok(())
+    let gate = args.fail_on.map(|fail_on| {
+        let findings = analysis.findings.as_ref().map(|findings| findings.findings.len());
+        GateResult::for_report(fail_on, source.description(), risk, findings, summary_path.display().to_string())
+    });
+    finish_gate(gate)
 } 
\ No newline at end of file
@@ -0,0 +1,192 @@
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{CompatConfig, RelevanceConfig};
+
+/// 프로필을 지정하지 않았을 때 사용하는 내장 프로필
+pub const DEFAULT_PROFILE: &str = "web-media-m38";
+
+/// 바이너리에 포함된 내장 프로필
+const BUILTIN_PROFILES: &[(&str, &str)] = &[
+    ("web-media-m38", include_str!("../profiles/web-media-m38.toml")),
+    ("rust-library", include_str!("../profiles/rust-library.toml")),
+    ("python-library", include_str!("../profiles/python-library.toml")),
+];
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileFile {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+    #[serde(default)]
+    compat: CompatConfig,
+    #[serde(default)]
+    relevance: RelevanceConfig,
+    templates: ProfileTemplates,
+}
+
+/// 프로필 템플릿 (`{{변수}}` 치환 전)
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileTemplates {
+    purpose: String,
+    criteria: String,
+    #[serde(default)]
+    checklist: String,
+    overview_criteria: String,
+    report_sections: String,
+    #[serde(default)]
+    update_checks: Vec<String>,
+    report_footer: String,
+}
+
+/// 분석 관점을 정의하는 프로필
+pub struct Profile {
+    pub name: String,
+    pub description: String,
+    /// 정적 호환성 검사의 기본 Chrome 기준 버전
+    pub chrome_baseline: Option<u32>,
+    /// 관련도 계산 키워드
+    relevance_keywords: Vec<String>,
+    variables: BTreeMap<String, String>,
+    templates: ProfileTemplates,
+}
+
+/// 변수 치환이 끝난 프로필 텍스트
+pub struct RenderedProfile {
+    pub name: String,
+    pub description: String,
+    pub purpose: String,
+    pub criteria: String,
+    pub checklist: String,
+    pub overview_criteria: String,
+    pub report_sections: String,
+    pub update_checks: Vec<String>,
+    pub report_footer: String,
+    /// diff가 예산보다 클 때 관련도를 높이는 키워드 (프로필과 설정 파일의 키워드)
+    pub relevance_keywords: Vec<String>,
+}
+
+impl Profile {
+    /// 이름 또는 파일 경로로 프로필 로드
+    ///
+    /// 탐색 순서: 파일 경로, `<프로젝트>/.diffanalyzer/profiles/<이름>.toml`,
+    /// `./profiles/<이름>.toml`, 내장 프로필
+    pub fn load(name_or_path: &str, project_path: &Path) -> Result<Profile> {
+        let file_name = format!("{}.toml", name_or_path);
+        let candidates = [
+            PathBuf::from(name_or_path),
+            project_path.join(".diffanalyzer").join("profiles").join(&file_name),
+            Path::new("profiles").join(&file_name),
+        ];
+
+        if let Some(path) = candidates.iter().find(|path| path.is_file()) {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("프로필 파일을 읽을 수 없습니다: {}", path.display()))?;
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| name_or_path.to_string());
+            return Self::parse(&name, &content)
+                .with_context(|| format!("프로필 형식이 올바르지 않습니다: {}", path.display()));
+        }
+
+        let (name, content) = BUILTIN_PROFILES
+            .iter()
+            .find(|(name, _)| *name == name_or_path)
+            .ok_or_else(|| {
+                anyhow!(
+                    "프로필을 찾을 수 없습니다: {} (내장 프로필: {})",
+                    name_or_path,
+                    builtin_profile_names().join(", ")
+                )
+            })?;
+        Self::parse(name, content)
+    }
+
+    fn parse(name: &str, content: &str) -> Result<Profile> {
+        let file: ProfileFile = toml::from_str(content)?;
+        Ok(Profile {
+            name: name.to_string(),
+            description: file.description,
+            chrome_baseline: file.compat.chrome,
+            relevance_keywords: file.relevance.keywords,
+            variables: file.variables,
+            templates: file.templates,
+        })
+    }
+
+    /// 템플릿 변수 치환
+    ///
+    /// 우선순위: 내장 변수(project, refs 등) < 프로필 `[variables]` < `overrides`
+    pub fn render(&self, builtins: &[(&str, &str)], overrides: &BTreeMap<String, String>) -> Result<RenderedProfile> {
+        let mut variables: BTreeMap<String, String> = builtins
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        variables.extend(self.variables.clone());
+        variables.extend(overrides.clone());
+
+        let render = |template: &str| {
+            render_template(template.trim(), &variables)
+                .with_context(|| format!("프로필 {} 템플릿 처리 실패", self.name))
+        };
+        let templates = &self.templates;
+
+        Ok(RenderedProfile {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            purpose: render(&templates.purpose)?,
+            criteria: render(&templates.criteria)?,
+            checklist: render(&templates.checklist)?,
+            overview_criteria: render(&templates.overview_criteria)?,
+            report_sections: render(&templates.report_sections)?,
+            update_checks: templates
+                .update_checks
+                .iter()
+                .map(|check| render(check))
+                .collect::<Result<_>>()?,
+            report_footer: render(&templates.report_footer)?,
+            relevance_keywords: self.relevance_keywords.clone(),
+        })
+    }
+}
+
+pub fn builtin_profile_names() -> Vec<&'static str> {
+    BUILTIN_PROFILES.iter().map(|(name, _)| *name).collect()
+}
+
+/// `{{이름}}` 형태의 변수를 치환 (정의되지 않은 변수는 오류)
+fn render_template(template: &str, variables: &BTreeMap<String, String>) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + 2 + end].trim();
+
+        // 식별자가 아닌 경우(코드 예시 등)는 그대로 둠
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            rendered.push_str(&rest[..start + 2]);
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        let value = variables
+            .get(key)
+            .ok_or_else(|| anyhow!("정의되지 않은 템플릿 변수입니다: {{{{{}}}}} (--var {}=값으로 지정하세요)", key, key))?;
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(value);
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    rendered.push_str(rest);
+    Ok(rendered)
+}
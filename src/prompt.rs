@@ -0,0 +1,329 @@
+use crate::diff::Diff;
+use crate::findings::json_schema;
+use crate::profile::RenderedProfile;
+
+/// 프롬프트 템플릿 버전 (템플릿을 바꾸면 올려서 이전 버전의 캐시된 응답을 무효화)
+pub const PROMPT_TEMPLATE_VERSION: u32 = 1;
+
+/// 분석 대상 (리비전 범위, 단일 커밋 또는 커밋되지 않은 변경사항)
+pub enum AnalysisTarget<'a> {
+    Range { project: &'a str, from: &'a str, to: &'a str, merge_base: bool },
+    Commit { project: &'a str, commit: &'a str },
+    Uncommitted { project: &'a str, staged: bool },
+}
+
+impl AnalysisTarget<'_> {
+    /// 프롬프트 첫 문장에 들어가는 대상 설명 (`프로젝트의 ... 변경사항`)
+    pub fn description(&self) -> String {
+        match self {
+            AnalysisTarget::Range { project, .. } => format!("{}의 {} 변경사항", project, self.range()),
+            AnalysisTarget::Commit { project, commit } => format!("{}의 커밋 {} 변경사항", project, commit),
+            AnalysisTarget::Uncommitted { project, .. } => format!("{}의 {}", project, self.uncommitted_label()),
+        }
+    }
+
+    /// 보고서 제목
+    pub fn report_title(&self) -> String {
+        match self {
+            AnalysisTarget::Range { project, .. } => format!("{} 변경사항 분석 ({}) - 사이드 이펙트 분석", project, self.range()),
+            AnalysisTarget::Commit { project, commit } => format!("{} 커밋 {} 변경사항 분석 - 사이드 이펙트 분석", project, commit),
+            AnalysisTarget::Uncommitted { project, .. } => format!("{} {} 분석 - 사이드 이펙트 분석", project, self.uncommitted_label()),
+        }
+    }
+
+    /// 보고서 개요의 "분석 대상" 항목
+    pub fn overview_target(&self) -> String {
+        match self {
+            AnalysisTarget::Range { project, .. } => format!("{} {}", project, self.range()),
+            AnalysisTarget::Commit { project, commit } => format!("{} 커밋 {}", project, commit),
+            AnalysisTarget::Uncommitted { project, .. } => format!("{} {} (HEAD 기준)", project, self.uncommitted_label()),
+        }
+    }
+
+    /// 테스트 시나리오와 확인 사항을 담는 섹션 제목
+    pub fn caution_heading(&self) -> &'static str {
+        match self {
+            AnalysisTarget::Range { .. } => "업데이트 시 주의사항",
+            AnalysisTarget::Commit { .. } => "커밋 적용 시 주의사항",
+            AnalysisTarget::Uncommitted { .. } => "커밋 전 주의사항",
+        }
+    }
+
+    /// 범위 표시 (`A → B`, 공통 조상 기준이면 표시 추가)
+    fn range(&self) -> String {
+        match self {
+            AnalysisTarget::Range { from, to, merge_base: false, .. } => format!("{} → {}", from, to),
+            AnalysisTarget::Range { from, to, merge_base: true, .. } => format!("{} → {} (공통 조상 기준)", from, to),
+            _ => String::new(),
+        }
+    }
+
+    fn uncommitted_label(&self) -> &'static str {
+        match self {
+            AnalysisTarget::Uncommitted { staged: true, .. } => "스테이징된 변경사항",
+            _ => "커밋되지 않은 변경사항",
+        }
+    }
+
+    fn data_label(&self) -> &'static str {
+        match self {
+            AnalysisTarget::Commit { .. } => "분석할 커밋 데이터",
+            _ => "분석할 diff 데이터",
+        }
+    }
+}
+
+/// 분석 대상과 프로필로 각 단계의 프롬프트를 생성
+pub struct PromptBuilder<'a> {
+    target: AnalysisTarget<'a>,
+    profile: &'a RenderedProfile,
+    /// 정적 검사 등으로 확인된 사실 (프롬프트에 그대로 포함)
+    static_findings: Option<&'a str>,
+    /// 최종 보고서를 마크다운 대신 JSON 발견 사항으로 요청
+    structured: bool,
+}
+
+impl<'a> PromptBuilder<'a> {
+    pub fn new(target: AnalysisTarget<'a>, profile: &'a RenderedProfile) -> Self {
+        Self { target, profile, static_findings: None, structured: false }
+    }
+
+    pub fn with_static_findings(mut self, findings: Option<&'a str>) -> Self {
+        self.static_findings = findings;
+        self
+    }
+
+    pub fn with_structured_output(mut self, structured: bool) -> Self {
+        self.structured = structured;
+        self
+    }
+
+    pub fn target(&self) -> &AnalysisTarget<'a> {
+        &self.target
+    }
+
+    /// 분석 목적, 기준, 체크리스트 (비어 있는 항목은 생략)
+    fn guidance(&self) -> String {
+        [&self.profile.purpose, &self.profile.criteria, &self.profile.checklist]
+            .into_iter()
+            .filter(|section| !section.is_empty())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// 정적 검사 결과 섹션 (없으면 빈 문자열)
+    fn static_findings(&self) -> String {
+        self.static_findings
+            .map(|findings| format!("\n\n{}", findings.trim_end()))
+            .unwrap_or_default()
+    }
+
+    fn update_checks(&self) -> String {
+        self.profile
+            .update_checks
+            .iter()
+            .map(|check| format!("\n- {}", check))
+            .collect()
+    }
+
+    /// 최종 마크다운 보고서 형식
+    fn report_format(&self) -> String {
+        let profile = self.profile;
+        match self.target {
+            AnalysisTarget::Range { project, .. } => format!(
+                "# {} 변경사항 분석 ({}) - 사이드 이펙트 분석
+
+## 📊 개요
+- 분석 대상: {} {}
+- 분석 목적: 라이브러리 사용자의 사이드 이펙트 방지
+- 분석 기준: {}
+
+{}
+
+## ⚠️ 업데이트 시 주의사항
+
+실제 변경이 있는 파일들에 대해:
+- 반드시 테스트해야 할 시나리오
+- 업데이트 전 확인 사항
+- 단계별 적용 권장사항{}
+
+{}",
+                project, self.target.range(),
+                project, self.target.range(),
+                profile.overview_criteria,
+                profile.report_sections,
+                self.update_checks(),
+                profile.report_footer,
+            ),
+            AnalysisTarget::Commit { project, commit } => format!(
+                "# {} 커밋 {} 변경사항 분석 - 사이드 이펙트 분석
+
+## 📊 개요
+- 분석 대상: {} 커밋 {}
+- 분석 목적: 라이브러리 사용자의 사이드 이펙트 방지
+- 분석 기준: {}
+
+{}
+
+## ⚠️ 커밋 적용 시 주의사항
+
+실제 변경이 있는 파일들에 대해:
+- 반드시 테스트해야 할 시나리오
+- 커밋 적용 전 확인 사항
+- 단계별 적용 권장사항{}
+
+{}",
+                project, commit,
+                project, commit,
+                profile.overview_criteria,
+                profile.report_sections,
+                self.update_checks(),
+                profile.report_footer,
+            ),
+            AnalysisTarget::Uncommitted { project, .. } => format!(
+                "# {} {} 분석 - 사이드 이펙트 분석
+
+## 📊 개요
+- 분석 대상: {} {} (HEAD 기준)
+- 분석 목적: 라이브러리 사용자의 사이드 이펙트 방지
+- 분석 기준: {}
+
+{}
+
+## ⚠️ 커밋 전 주의사항
+
+실제 변경이 있는 파일들에 대해:
+- 반드시 테스트해야 할 시나리오
+- 커밋/푸시 전 확인 사항
+- 단계별 적용 권장사항{}
+
+{}",
+                project, self.target.uncommitted_label(),
+                project, self.target.uncommitted_label(),
+                profile.overview_criteria,
+                profile.report_sections,
+                self.update_checks(),
+                profile.report_footer,
+            ),
+        }
+    }
+
+    /// 최종 보고서 출력 형식 안내 (마크다운 보고서 형식 또는 JSON 스키마)
+    fn output_instructions(&self) -> String {
+        if !self.structured {
+            return format!("다음 형식으로 마크다운 분석 보고서를 작성해주세요:\n\n{}", self.report_format());
+        }
+
+        format!(
+            "마크다운 보고서 대신 아래 JSON 스키마에 맞는 JSON 객체 하나만 출력해주세요. 보고서는 도구가 이 결과로 작성합니다.
+
+- summary: 변경사항 전체 요약 (2~3문장)
+- change_scale: 변경 규모 (예: \"파일 12개, 재생 로직 중심의 중간 규모 변경\")
+- overall_risk: 사이드 이펙트 리스크 (high/medium/low)
+- update_recommendation: 업데이트 권장도 (immediate: 즉시, after_testing: 테스트 후, careful: 신중히)
+- findings: 사용자에게 영향을 주는 변경사항과 문제마다 하나씩 (분석 기준: {})
+  - file, line: 파일 경로와 변경 후 줄 번호 (줄 번호를 모르면 null)
+  - category: compatibility(호환성), behavior(동작 변경), api(API 변경), performance(성능), media(미디어 재생), security(보안), other(기타)
+  - risk, title, description: 리스크 수준, 한 줄 제목, 구체적인 문제점과 근거
+  - code: 문제가 되는 코드 (없으면 null)
+  - affected_browsers: 문제가 발생하는 브라우저/런타임 버전 (예: [\"Chrome 38-49\"], 해당 없으면 빈 배열)
+  - suggested_fix: 제안 코드 또는 대응 방법 (없으면 null)
+- test_scenarios: {}에 들어갈 반드시 테스트해야 할 시나리오와 확인 사항{}
+- recommendations: 결론 및 권장사항 (안전한 업데이트 전략, 즉시 수정이 필요한 문제 등)
+
+JSON 스키마:
+{}",
+            self.profile.overview_criteria,
+            self.target.caution_heading(),
+            self.update_checks(),
+            json_schema()
+        )
+    }
+
+    /// 단일 요청으로 보고서를 작성하는 프롬프트
+    pub fn analysis_prompt(&self, analysis_content: &str) -> String {
+        format!(
+            "{}을 라이브러리 사용자 관점에서 분석해주세요.
+
+{}{}
+
+{}
+
+**{}:**
+{}",
+            self.target.description(),
+            self.guidance(),
+            self.static_findings(),
+            self.output_instructions(),
+            self.target.data_label(),
+            analysis_content
+        )
+    }
+
+    /// 청크 분석(map) 단계 프롬프트: 일부 diff에 대한 중간 분석 결과 요청
+    pub fn chunk_analysis_prompt(&self, index: usize, total: usize, chunk_content: &str) -> String {
+        format!(
+            "{} 중 일부(전체 {}개 부분 중 {}번째)입니다. 최종 보고서는 모든 부분의 결과를 종합해 별도로 작성하므로, 이 부분에 포함된 변경사항만 간결하게 중간 분석해주세요.
+
+{}
+
+다음 형식으로 작성해주세요 (해당 사항이 없는 항목은 \"없음\"으로 표기):
+
+### 부분 {}/{} 분석
+#### 포함된 파일
+#### 동작 변경사항 (파일명과 구체적인 코드 변경 내용)
+#### 호환성 문제 (문제 코드, 문제가 발생하는 런타임/버전, 제안 코드)
+#### 분석 기준별 영향 (위 분석 목적과 기준에서 중요하게 보는 관점)
+#### 라이브러리 사용자 영향
+#### 리스크 평가 (높음/중간/낮음과 근거)
+
+**{} (부분 {}/{}):**
+{}",
+            self.target.description(), total, index,
+            self.guidance(),
+            index, total,
+            self.target.data_label(), index, total,
+            chunk_content
+        )
+    }
+
+    /// 중간 결과가 너무 많을 때 여러 중간 결과를 하나로 합치는 프롬프트
+    pub fn merge_prompt(&self, partial_reports: &[String]) -> String {
+        format!(
+            "다음은 {}을 여러 부분으로 나누어 분석한 중간 결과들입니다. 같은 형식을 유지하면서 하나의 중간 분석 결과로 합쳐주세요. 파일명, 코드, 런타임 버전, 리스크 근거 등 구체적인 정보는 생략하지 마세요.
+
+{}",
+            self.target.description(),
+            partial_reports.join("\n\n---\n\n")
+        )
+    }
+
+    /// 청크 분석(reduce) 단계 프롬프트: 중간 결과를 종합해 최종 보고서 작성
+    ///
+    /// `chunk_count`는 처음 나눈 청크 수 (중간 결과는 여러 단계로 합쳐져 그보다 적을 수 있음)
+    pub fn reduce_prompt(&self, diff: &Diff, chunk_count: usize, partial_reports: &[String]) -> String {
+        format!(
+            "{}을 라이브러리 사용자 관점에서 분석해주세요.
+
+전체 diff가 커서 {}개 부분으로 나누어 먼저 분석했습니다. 아래 부분별 분석 결과를 종합하여 하나의 최종 보고서를 작성해주세요. 중복된 내용은 합치고, 부분 결과에 없는 내용은 추측하지 마세요.
+
+{}{}
+
+{}
+
+**전체 통계:** {}
+{}
+**부분별 분석 결과:**
+{}",
+            self.target.description(),
+            chunk_count,
+            self.guidance(),
+            self.static_findings(),
+            self.output_instructions(),
+            diff.stats_line(),
+            diff.file_list(),
+            partial_reports.join("\n\n---\n\n")
+        )
+    }
+}
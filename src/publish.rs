@@ -0,0 +1,368 @@
+use anyhow::{Result, anyhow};
+use clap::ValueEnum;
+use reqwest::{Client, Method, RequestBuilder};
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+use crate::provider::{ApiError, required_env};
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+/// 댓글 목록 한 페이지의 항목 수 (두 API 모두 최대 100)
+const COMMENTS_PER_PAGE: usize = 100;
+/// 댓글 API 요청 하나의 제한 시간
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// 잘린 보고서 뒤에 붙이는 안내
+const TRUNCATION_NOTICE: &str = "\n\n> ⚠️ 댓글 길이 제한으로 보고서 일부가 생략되었습니다. 전체 보고서는 CI 산출물의 요약 파일을 확인해주세요.\n";
+
+/// 댓글을 게시할 코드 호스팅 서비스
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Platform {
+    /// GitHub Pull Request (Issues 댓글 API)
+    Github,
+    /// GitLab Merge Request (Notes API)
+    Gitlab,
+}
+
+impl Platform {
+    fn name(self) -> &'static str {
+        match self {
+            Platform::Github => "GitHub",
+            Platform::Gitlab => "GitLab",
+        }
+    }
+
+    fn default_api_url(self) -> &'static str {
+        match self {
+            Platform::Github => GITHUB_API_URL,
+            Platform::Gitlab => GITLAB_API_URL,
+        }
+    }
+
+    /// 인증 토큰을 읽는 환경변수
+    pub fn token_env(self) -> &'static str {
+        match self {
+            Platform::Github => "GITHUB_TOKEN",
+            Platform::Gitlab => "GITLAB_TOKEN",
+        }
+    }
+
+    /// 댓글 본문 최대 길이 (문자 수, GitHub 65,536 / GitLab 1,000,000)
+    pub fn max_comment_chars(self) -> usize {
+        match self {
+            Platform::Github => 65_536,
+            Platform::Gitlab => 1_000_000,
+        }
+    }
+}
+
+/// 댓글 게시 결과
+pub enum PublishOutcome {
+    Created(u64),
+    Updated(u64),
+}
+
+#[derive(Deserialize)]
+struct Comment {
+    id: u64,
+    /// GitLab 시스템 노트 등은 본문이 없을 수 있음
+    body: Option<String>,
+}
+
+/// PR/MR 하나에 분석 보고서를 고정 댓글 하나로 게시
+///
+/// 댓글 첫 줄에 숨김 표시(`<!-- git-diff-analyzer:<marker> -->`)를 넣고, 같은 표시가 있는 댓글이 있으면
+/// 새 댓글을 만들지 않고 그 댓글을 수정함
+pub struct CommentPublisher {
+    client: Client,
+    platform: Platform,
+    api_url: String,
+    /// GitHub은 `OWNER/REPO`, GitLab은 프로젝트 경로(`group/project`) 또는 숫자 ID
+    repo: String,
+    /// PR 번호 또는 MR IID
+    number: u64,
+    token: String,
+}
+
+impl CommentPublisher {
+    /// 인증 토큰은 플랫폼별 환경변수(`GITHUB_TOKEN`, `GITLAB_TOKEN`)에서 읽음
+    pub fn new(platform: Platform, api_url: Option<String>, repo: String, number: u64) -> Result<Self> {
+        Self::with_token(platform, api_url, repo, number, required_env(platform.token_env())?)
+    }
+
+    pub fn with_token(platform: Platform, api_url: Option<String>, repo: String, number: u64, token: String) -> Result<Self> {
+        if platform == Platform::Github && repo.split('/').filter(|part| !part.is_empty()).count() != 2 {
+            return Err(anyhow!("GitHub 저장소는 OWNER/REPO 형식으로 지정해주세요: {}", repo));
+        }
+        Ok(Self {
+            client: Client::builder().timeout(REQUEST_TIMEOUT).build()?,
+            platform,
+            api_url: api_url.unwrap_or_else(|| platform.default_api_url().to_string()),
+            repo,
+            number,
+            token,
+        })
+    }
+
+    /// 보고서를 게시하고 (길이 제한을 넘으면 잘라서) 만들거나 수정한 댓글 ID를 반환
+    pub async fn publish(&self, report: &str, marker: &str) -> Result<PublishOutcome> {
+        let marker_line = format!("<!-- git-diff-analyzer:{} -->", marker);
+        let max_chars = self.platform.max_comment_chars().saturating_sub(marker_line.chars().count() + 1);
+        let body = format!("{}\n{}", marker_line, truncate_report(report, max_chars));
+        let payload = json!({ "body": body });
+
+        match self.find_comment(&marker_line).await? {
+            Some(id) => {
+                let method = match self.platform {
+                    Platform::Github => Method::PATCH,
+                    Platform::Gitlab => Method::PUT,
+                };
+                self.send(self.request(method, &self.comment_url(id)).json(&payload)).await?;
+                Ok(PublishOutcome::Updated(id))
+            }
+            None => {
+                let response = self.send(self.request(Method::POST, &self.comments_url()).json(&payload)).await?;
+                let comment: Comment = response.json().await?;
+                Ok(PublishOutcome::Created(comment.id))
+            }
+        }
+    }
+
+    /// 표시가 있는 기존 댓글 ID (여러 개면 가장 먼저 작성된 댓글)
+    async fn find_comment(&self, marker_line: &str) -> Result<Option<u64>> {
+        for page in 1.. {
+            let url = format!("{}?per_page={}&page={}", self.comments_url(), COMMENTS_PER_PAGE, page);
+            let response = self.send(self.request(Method::GET, &url)).await?;
+            let comments: Vec<Comment> = response.json().await?;
+
+            let found = comments
+                .iter()
+                .find(|comment| comment.body.as_deref().is_some_and(|body| body.starts_with(marker_line)));
+            if let Some(comment) = found {
+                return Ok(Some(comment.id));
+            }
+            if comments.len() < COMMENTS_PER_PAGE {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    fn comments_url(&self) -> String {
+        let api_url = self.api_url.trim_end_matches('/');
+        match self.platform {
+            Platform::Github => format!("{}/repos/{}/issues/{}/comments", api_url, self.repo, self.number),
+            Platform::Gitlab => format!(
+                "{}/projects/{}/merge_requests/{}/notes",
+                api_url,
+                encode_path_segment(&self.repo),
+                self.number
+            ),
+        }
+    }
+
+    fn comment_url(&self, id: u64) -> String {
+        let api_url = self.api_url.trim_end_matches('/');
+        match self.platform {
+            Platform::Github => format!("{}/repos/{}/issues/comments/{}", api_url, self.repo, id),
+            Platform::Gitlab => format!("{}/{}", self.comments_url(), id),
+        }
+    }
+
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let builder = self.client.request(method, url);
+        match self.platform {
+            Platform::Github => builder
+                .bearer_auth(&self.token)
+                .header("Accept", "application/vnd.github+json")
+                .header("X-GitHub-Api-Version", "2022-11-28")
+                .header("User-Agent", "git-diff-analyzer"),
+            Platform::Gitlab => builder.header("PRIVATE-TOKEN", &self.token),
+        }
+    }
+
+    async fn send(&self, builder: RequestBuilder) -> Result<reqwest::Response> {
+        let response = builder.send().await?;
+        if !response.status().is_success() {
+            return Err(ApiError {
+                provider: self.platform.name(),
+                status: response.status().as_u16(),
+                retry_after: None,
+                body: response.text().await.unwrap_or_default(),
+            }
+            .into());
+        }
+        Ok(response)
+    }
+}
+
+/// 댓글 길이 제한에 맞춰 보고서를 줄 단위로 자름 (닫히지 않은 코드 블록은 닫고 안내 추가)
+fn truncate_report(report: &str, max_chars: usize) -> String {
+    if report.chars().count() <= max_chars {
+        return report.to_string();
+    }
+
+    // 안내와 코드 블록을 닫는 줄이 들어갈 자리를 남김
+    let budget = max_chars.saturating_sub(TRUNCATION_NOTICE.chars().count() + 10);
+    let mut truncated = String::new();
+    let mut used = 0;
+    // 열려 있는 코드 블록 펜스의 백틱 수
+    let mut open_fence: Option<usize> = None;
+    for line in report.lines() {
+        let line_chars = line.chars().count() + 1;
+        if used + line_chars > budget {
+            // 첫 줄부터 너무 길면 문자 단위로 자름
+            if truncated.is_empty() {
+                truncated = line.chars().take(budget).collect();
+                truncated.push('\n');
+            }
+            break;
+        }
+        let trimmed = line.trim_start();
+        let backticks = trimmed.chars().take_while(|&c| c == '`').count();
+        if backticks >= 3 {
+            open_fence = match open_fence {
+                None => Some(backticks),
+                Some(fence) if backticks >= fence && trimmed[backticks..].trim().is_empty() => None,
+                still_open => still_open,
+            };
+        }
+        truncated.push_str(line);
+        truncated.push('\n');
+        used += line_chars;
+    }
+
+    if let Some(fence) = open_fence {
+        truncated.push_str(&"`".repeat(fence));
+        truncated.push('\n');
+    }
+    truncated.push_str(TRUNCATION_NOTICE);
+    truncated
+}
+
+/// GitLab 프로젝트 경로를 URL 경로 한 칸으로 인코딩 (`group/project` → `group%2Fproject`)
+fn encode_path_segment(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// 받은 요청 (메서드, 경로, 본문)
+    type Requests = Vec<(String, String, String)>;
+
+    /// 요청마다 순서대로 정해진 JSON 응답을 돌려주는 모의 API 서버
+    async fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Requests>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = Vec::new();
+                let mut chunk = [0u8; 4096];
+                let header_end = loop {
+                    let read = socket.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..read]);
+                    if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                        break end + 4;
+                    }
+                };
+                let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+                let content_length = head
+                    .lines()
+                    .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|value| value.trim().parse().unwrap()))
+                    .unwrap_or(0);
+                while buffer.len() < header_end + content_length {
+                    let read = socket.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..read]);
+                }
+                let mut request_line = head.lines().next().unwrap().split(' ');
+                let (method, path) = (request_line.next().unwrap().to_string(), request_line.next().unwrap().to_string());
+                requests.push((method, path, String::from_utf8_lossy(&buffer[header_end..]).into_owned()));
+
+                let response = format!(
+                    "HTTP/1.1 {} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn updates_existing_sticky_comment() {
+        let (url, server) = mock_server(vec![
+            (200, r#"[{"id":1,"body":"LGTM"},{"id":7,"body":"<!-- git-diff-analyzer:default -->\n이전 보고서"}]"#),
+            (200, r#"{"id":7}"#),
+        ])
+        .await;
+        let publisher = CommentPublisher::with_token(Platform::Github, Some(url), "acme/player".into(), 42, "token".into()).unwrap();
+
+        let outcome = publisher.publish("# 보고서", "default").await.unwrap();
+        assert!(matches!(outcome, PublishOutcome::Updated(7)));
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests[0].0, "GET");
+        assert_eq!(requests[0].1, "/repos/acme/player/issues/42/comments?per_page=100&page=1");
+        assert_eq!((requests[1].0.as_str(), requests[1].1.as_str()), ("PATCH", "/repos/acme/player/issues/comments/7"));
+        let body: serde_json::Value = serde_json::from_str(&requests[1].2).unwrap();
+        assert_eq!(body["body"], "<!-- git-diff-analyzer:default -->\n# 보고서");
+    }
+
+    #[tokio::test]
+    async fn creates_comment_when_marker_differs() {
+        let (url, server) = mock_server(vec![
+            (200, r#"[{"id":3,"body":"<!-- git-diff-analyzer:nightly -->\n보고서"},{"id":4,"body":null}]"#),
+            (201, r#"{"id":9}"#),
+        ])
+        .await;
+        let publisher = CommentPublisher::with_token(Platform::Gitlab, Some(url), "group/player".into(), 7, "token".into()).unwrap();
+
+        let outcome = publisher.publish("# 보고서", "default").await.unwrap();
+        assert!(matches!(outcome, PublishOutcome::Created(9)));
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests[0].1, "/projects/group%2Fplayer/merge_requests/7/notes?per_page=100&page=1");
+        assert_eq!((requests[1].0.as_str(), requests[1].1.as_str()), ("POST", "/projects/group%2Fplayer/merge_requests/7/notes"));
+    }
+
+    #[test]
+    fn short_report_is_not_truncated() {
+        assert_eq!(truncate_report("# 보고서\n내용\n", 100), "# 보고서\n내용\n");
+    }
+
+    #[test]
+    fn truncates_at_line_boundary() {
+        let report: String = (0..100).map(|line| format!("줄 {}\n", line)).collect();
+        let truncated = truncate_report(&report, 300);
+        assert!(truncated.chars().count() <= 300);
+        assert!(truncated.starts_with("줄 0\n줄 1\n"));
+        assert!(truncated.ends_with(TRUNCATION_NOTICE));
+        let kept = truncated.strip_suffix(TRUNCATION_NOTICE).unwrap();
+        assert!(kept.lines().all(|line| line.starts_with("줄 ")));
+    }
+
+    #[test]
+    fn closes_open_code_fence() {
+        let report = format!("# 보고서\n````js\n```\n{}", "x.at(-1);\n".repeat(100));
+        let truncated = truncate_report(&report, 400);
+        let kept = truncated.strip_suffix(TRUNCATION_NOTICE).unwrap();
+        assert!(kept.ends_with("\n````\n"));
+    }
+}
@@ -0,0 +1,409 @@
+use std::path::Path;
+
+use crate::diff::{Diff, FileDiff, LineKind};
+use crate::tokenizer::TokenCounter;
+
+/// 진입점(공개 API의 시작점)으로 보는 파일 이름 (확장자 제외)
+const ENTRY_POINT_STEMS: &[&str] = &["index", "main", "lib", "mod", "__init__"];
+/// 진입점 가산점
+const ENTRY_POINT_SCORE: u32 = 20;
+/// 키워드 하나당 가산점과 최대 가산점
+const KEYWORD_SCORE: u32 = 15;
+const MAX_KEYWORD_SCORE: u32 = 45;
+/// 변경 줄 수 가산점 (이 줄 수마다 1점, 최대 가산점까지)
+const CHURN_LINES_PER_POINT: usize = 20;
+const MAX_CHURN_SCORE: u32 = 20;
+/// 생략 파일 목록에 쓰는 최대 예산 비율 (1/N, 들어가지 않는 파일은 개수만 표시)
+const DROPPED_LIST_SHARE: usize = 4;
+
+/// 관련도 계산에 쓰는 파일 분류
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileCategory {
+    Source,
+    Build,
+    Test,
+    Docs,
+    Other,
+}
+
+impl FileCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            FileCategory::Source => "소스",
+            FileCategory::Build => "빌드 설정",
+            FileCategory::Test => "테스트",
+            FileCategory::Docs => "문서",
+            FileCategory::Other => "기타",
+        }
+    }
+
+    fn base_score(self) -> u32 {
+        match self {
+            FileCategory::Source => 60,
+            FileCategory::Build => 30,
+            FileCategory::Other => 20,
+            FileCategory::Test => 15,
+            FileCategory::Docs => 10,
+        }
+    }
+
+    fn of(path: &Path) -> Self {
+        let name = path.file_name().map(|name| name.to_string_lossy().to_lowercase()).unwrap_or_default();
+        let extension = path.extension().map(|extension| extension.to_string_lossy().to_lowercase()).unwrap_or_default();
+        let in_dir = |names: &[&str]| {
+            path.parent()
+                .is_some_and(|parent| parent.components().any(|component| names.contains(&&*component.as_os_str().to_string_lossy())))
+        };
+
+        if in_dir(&["test", "tests", "__tests__", "spec", "e2e", "fixtures"])
+            || [".test.", ".spec.", "_test."].iter().any(|marker| name.contains(marker))
+            || name.starts_with("test_")
+        {
+            return FileCategory::Test;
+        }
+        if in_dir(&["docs", "doc", "examples", "example"])
+            || matches!(extension.as_str(), "md" | "mdx" | "rst" | "adoc" | "txt")
+            || name.starts_with("license")
+            || name.starts_with("changelog")
+        {
+            return FileCategory::Docs;
+        }
+        if in_dir(&[".github", ".gitlab", ".circleci"])
+            || matches!(extension.as_str(), "json" | "toml" | "yml" | "yaml" | "lock" | "cfg" | "ini" | "gradle")
+            || name.contains(".config.")
+            || matches!(name.as_str(), "makefile" | "dockerfile" | "cmakelists.txt" | "build.rs" | "setup.py")
+        {
+            return FileCategory::Build;
+        }
+        if matches!(
+            extension.as_str(),
+            "js" | "mjs" | "cjs" | "jsx" | "ts" | "mts" | "cts" | "tsx" | "rs" | "py" | "go" | "c" | "cc" | "cpp" | "h" | "hpp"
+                | "java" | "kt" | "swift" | "m" | "mm" | "cs" | "rb" | "php" | "css" | "scss" | "html" | "vue" | "svelte"
+        ) {
+            return FileCategory::Source;
+        }
+        FileCategory::Other
+    }
+}
+
+/// 관련도 점수를 매긴 diff 파일
+#[derive(Clone, Debug)]
+pub struct RankedFile {
+    /// `Diff::files`에서의 위치
+    pub index: usize,
+    pub path: String,
+    pub category: FileCategory,
+    pub score: u32,
+    /// 분류 외의 가산점 근거 (진입점, 키워드 등)
+    pub reasons: Vec<String>,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl RankedFile {
+    /// 보고서에 표시할 점수와 근거 (`95 (진입점, 키워드 MediaSource)`)
+    pub fn score_label(&self) -> String {
+        match self.reasons.is_empty() {
+            true => self.score.to_string(),
+            false => format!("{} ({})", self.score, self.reasons.join(", ")),
+        }
+    }
+}
+
+/// 파일 분류, 진입점 여부, 프로필 키워드, 변경 규모로 diff 파일의 관련도를 계산
+pub struct RelevanceScorer<'a> {
+    keywords: &'a [String],
+}
+
+impl<'a> RelevanceScorer<'a> {
+    pub fn new(keywords: &'a [String]) -> Self {
+        Self { keywords }
+    }
+
+    /// 관련도가 높은 순으로 정렬한 파일 (점수가 같으면 diff 순서 유지)
+    pub fn rank(&self, diff: &Diff) -> Vec<RankedFile> {
+        let mut ranked: Vec<RankedFile> = diff.files.iter().enumerate().map(|(index, file)| self.score(index, file)).collect();
+        ranked.sort_by_key(|file| std::cmp::Reverse(file.score));
+        ranked
+    }
+
+    fn score(&self, index: usize, file: &FileDiff) -> RankedFile {
+        let category = FileCategory::of(file.path());
+        let mut score = category.base_score();
+        let mut reasons = Vec::new();
+
+        let is_entry_point = file
+            .path()
+            .file_stem()
+            .is_some_and(|stem| ENTRY_POINT_STEMS.contains(&&*stem.to_string_lossy()));
+        if category == FileCategory::Source && is_entry_point {
+            score += ENTRY_POINT_SCORE;
+            reasons.push("진입점".to_string());
+        }
+
+        // 바뀐 줄이나 경로에 나오는 키워드 (컨텍스트 줄은 제외)
+        let path = file.path().to_string_lossy();
+        let matched: Vec<&str> = self
+            .keywords
+            .iter()
+            .filter(|keyword| {
+                path.contains(keyword.as_str())
+                    || file
+                        .hunks
+                        .iter()
+                        .flat_map(|hunk| &hunk.lines)
+                        .any(|line| line.kind != LineKind::Context && line.content.contains(keyword.as_str()))
+            })
+            .map(String::as_str)
+            .collect();
+        if !matched.is_empty() {
+            score += (matched.len() as u32 * KEYWORD_SCORE).min(MAX_KEYWORD_SCORE);
+            reasons.push(format!("키워드 {}", matched.join(", ")));
+        }
+
+        let (added, removed) = (file.added_lines(), file.removed_lines());
+        score += ((added + removed) / CHURN_LINES_PER_POINT).min(MAX_CHURN_SCORE as usize) as u32;
+
+        RankedFile { index, path: file.display_path(), category, score, reasons, added, removed }
+    }
+}
+
+/// 토큰 예산 안에서 관련도 순으로 고른 diff 내용
+pub struct Prioritized {
+    /// 관련도 순으로 이어 붙인 diff 내용
+    pub content: String,
+    /// 내용을 모두 포함한 파일 수
+    pub included: usize,
+    /// 예산에 들어가지 않아 앞부분만 포함한 파일
+    pub partial: Option<RankedFile>,
+    /// 예산에 들어가지 않아 내용을 생략한 파일 (관련도 순)
+    pub dropped: Vec<RankedFile>,
+    /// 프롬프트에 넣는 생략 파일 목록에 남겨둔 예산
+    list_tokens: usize,
+    tokens: TokenCounter,
+}
+
+impl Prioritized {
+    /// 관련도가 높은 파일부터 예산에 들어가는 파일을 통째로 포함
+    ///
+    /// 들어가지 않는 파일은 건너뛰고 다음 파일을 시도함. 가장 관련도가 높은 파일조차 들어가지 않으면
+    /// 그 파일의 앞부분만 포함. `max_tokens`는 생략 파일 목록(`prompt_section`)까지 포함한 예산으로,
+    /// 목록에는 모든 파일을 생략하는 경우의 길이와 예산의 1/`DROPPED_LIST_SHARE` 중 작은 만큼을 남겨둠
+    pub fn select(diff: &Diff, ranked: Vec<RankedFile>, max_tokens: usize, tokens: TokenCounter) -> Self {
+        let list_tokens = tokens
+            .count(&dropped_section(None, &ranked, usize::MAX, tokens))
+            .min(max_tokens / DROPPED_LIST_SHARE);
+        let max_tokens = max_tokens.saturating_sub(list_tokens);
+
+        let mut selection = Self {
+            content: diff.preamble.clone(),
+            included: 0,
+            partial: None,
+            dropped: Vec::new(),
+            list_tokens,
+            tokens,
+        };
+        let mut used_tokens = tokens.count(&diff.preamble);
+        for file in ranked {
+            let patch = diff.files[file.index].to_patch();
+            let patch_tokens = tokens.count(&patch);
+            if used_tokens + patch_tokens <= max_tokens {
+                selection.content.push_str(&patch);
+                selection.included += 1;
+                used_tokens += patch_tokens;
+            } else if selection.included == 0 && selection.partial.is_none() {
+                used_tokens += truncate_lines(&patch, max_tokens.saturating_sub(used_tokens), tokens, &mut selection.content);
+                selection.partial = Some(file);
+            } else {
+                selection.dropped.push(file);
+            }
+        }
+        selection
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.partial.is_none() && self.dropped.is_empty()
+    }
+
+    /// 프롬프트에 넣는 생략 파일 목록 (남겨둔 예산을 넘는 파일은 개수만 표시)
+    pub fn prompt_section(&self) -> String {
+        dropped_section(self.partial.as_ref(), &self.dropped, self.list_tokens, self.tokens)
+    }
+
+    /// 보고서에 첨부하는 생략 파일 표
+    pub fn markdown(&self, model: &str) -> String {
+        let mut report = String::from("## ✂️ 토큰 예산으로 생략한 파일\n\n");
+        report.push_str(&format!(
+            "{}의 토큰 예산에 diff 전체가 들어가지 않아 관련도가 높은 파일 {}개를 먼저 분석했습니다. 아래 파일의 변경 내용은 분석에 포함되지 않았으니 직접 확인해주세요.\n\n",
+            model, self.included
+        ));
+        report.push_str("| 파일 | 분류 | 관련도 | 변경 | 포함 여부 |\n|---|---|---|---|---|\n");
+        let rows = self.partial.iter().map(|file| (file, "앞부분만 포함")).chain(self.dropped.iter().map(|file| (file, "생략")));
+        for (file, inclusion) in rows {
+            report.push_str(&format!(
+                "| `{}` | {} | {} | +{} -{} | {} |\n",
+                file.path.replace('|', "\\|"),
+                file.category.label(),
+                file.score_label().replace('|', "\\|"),
+                file.added,
+                file.removed,
+                inclusion
+            ));
+        }
+        report
+    }
+}
+
+/// 생략 파일 목록 (`max_tokens`에 들어가지 않는 나머지 파일은 `외 N개` 한 줄로 표시)
+fn dropped_section(partial: Option<&RankedFile>, dropped: &[RankedFile], max_tokens: usize, tokens: TokenCounter) -> String {
+    let mut section = String::from("=== 토큰 예산으로 내용을 생략한 파일 (아래 diff에 없음, 변경 사실만 참고) ===\n");
+    let lines: Vec<String> = partial
+        .map(|file| format!("{} (+{} -{}, 앞부분만 포함)\n", file.path, file.added, file.removed))
+        .into_iter()
+        .chain(dropped.iter().map(|file| format!("{} (+{} -{}, {})\n", file.path, file.added, file.removed, file.category.label())))
+        .collect();
+
+    let omitted_line = |count: usize| format!("외 {}개 파일\n", count);
+    let mut used_tokens = tokens.count(&section);
+    for (index, line) in lines.iter().enumerate() {
+        // 이 줄을 넣고도 나머지 파일 수를 표시할 자리가 남아야 함
+        let remaining = lines.len() - index - 1;
+        let line_tokens = tokens.count(line);
+        let reserve = match remaining {
+            0 => 0,
+            _ => tokens.count(&omitted_line(remaining)),
+        };
+        if used_tokens + line_tokens + reserve > max_tokens {
+            section.push_str(&omitted_line(lines.len() - index));
+            break;
+        }
+        section.push_str(line);
+        used_tokens += line_tokens;
+    }
+    section
+}
+
+/// 예산에 들어가는 줄까지만 붙이고 사용한 토큰 수를 반환
+fn truncate_lines(patch: &str, max_tokens: usize, tokens: TokenCounter, out: &mut String) -> usize {
+    let mut used_tokens = 0;
+    for line in patch.lines() {
+        let line_tokens = tokens.count(line) + 1;
+        if used_tokens + line_tokens >= max_tokens {
+            out.push_str("... (토큰 제한으로 나머지 내용 생략)\n");
+            break;
+        }
+        out.push_str(line);
+        out.push('\n');
+        used_tokens += line_tokens;
+    }
+    used_tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_patch(path: &str, lines: usize) -> String {
+        let mut patch = format!("diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -1,{lines} +1,{lines} @@\n");
+        for index in 0..lines {
+            patch.push_str(&format!("-old line {}\n+new line {}\n", index, index));
+        }
+        patch
+    }
+
+    fn diff(files: &[(&str, usize)]) -> Diff {
+        let patch: String = files.iter().map(|(path, lines)| file_patch(path, *lines)).collect();
+        Diff::parse(patch.as_bytes())
+    }
+
+    fn ranked_paths(ranked: &[RankedFile]) -> Vec<&str> {
+        ranked.iter().map(|file| file.path.as_str()).collect()
+    }
+
+    #[test]
+    fn categorizes_paths() {
+        assert_eq!(FileCategory::of(Path::new("src/player.js")), FileCategory::Source);
+        assert_eq!(FileCategory::of(Path::new("tests/player.js")), FileCategory::Test);
+        assert_eq!(FileCategory::of(Path::new("src/player.test.ts")), FileCategory::Test);
+        assert_eq!(FileCategory::of(Path::new("docs/guide.js")), FileCategory::Docs);
+        assert_eq!(FileCategory::of(Path::new("CHANGELOG")), FileCategory::Docs);
+        assert_eq!(FileCategory::of(Path::new("package.json")), FileCategory::Build);
+        assert_eq!(FileCategory::of(Path::new(".github/workflows/ci.sh")), FileCategory::Build);
+        assert_eq!(FileCategory::of(Path::new("assets/logo.svg")), FileCategory::Other);
+    }
+
+    #[test]
+    fn ranks_by_category() {
+        let diff = diff(&[("README.md", 1), ("tests/a.js", 1), ("Cargo.toml", 1), ("src/a.rs", 1), ("data.bin", 1)]);
+        let ranked = RelevanceScorer::new(&[]).rank(&diff);
+        assert_eq!(ranked_paths(&ranked), vec!["src/a.rs", "Cargo.toml", "data.bin", "tests/a.js", "README.md"]);
+    }
+
+    #[test]
+    fn entry_point_and_keyword_bonuses() {
+        let keywords = vec!["MediaSource".to_string()];
+        let patch = [
+            file_patch("src/util.js", 1),
+            file_patch("src/index.js", 1),
+            file_patch("src/media.js", 1).replace("+new line 0", "+const source = new MediaSource();"),
+            file_patch("tests/index.js", 1),
+        ]
+        .concat();
+        let ranked = RelevanceScorer::new(&keywords).rank(&Diff::parse(patch.as_bytes()));
+
+        assert_eq!(ranked_paths(&ranked), vec!["src/index.js", "src/media.js", "src/util.js", "tests/index.js"]);
+        assert_eq!(ranked[0].score_label(), format!("{} (진입점)", 60 + ENTRY_POINT_SCORE));
+        assert_eq!(ranked[1].score_label(), format!("{} (키워드 MediaSource)", 60 + KEYWORD_SCORE));
+        // 진입점 가산점은 소스 파일에만 적용
+        assert!(ranked[3].reasons.is_empty());
+    }
+
+    #[test]
+    fn keyword_bonus_is_capped() {
+        let keywords: Vec<String> = ["alpha", "beta", "gamma", "delta"].map(String::from).to_vec();
+        let diff = diff(&[("src/alpha_beta_gamma_delta.js", 1)]);
+        let ranked = RelevanceScorer::new(&keywords).rank(&diff);
+        assert_eq!(ranked[0].score, 60 + MAX_KEYWORD_SCORE);
+    }
+
+    #[test]
+    fn many_dropped_files_do_not_starve_content() {
+        // 생략 목록이 예산보다 길어도 관련도가 가장 높은 파일은 포함
+        let mut files = vec![("src/index.js", 2)];
+        let names: Vec<String> = (0..400).map(|index| format!("docs/very/long/path/to/generated/page-{}.md", index)).collect();
+        files.extend(names.iter().map(|name| (name.as_str(), 20)));
+        let diff = diff(&files);
+        let tokens = TokenCounter::for_model("gpt-4o");
+        let ranked = RelevanceScorer::new(&[]).rank(&diff);
+
+        let selection = Prioritized::select(&diff, ranked, 1000, tokens);
+        assert!(selection.included >= 1);
+        assert!(selection.content.contains("src/index.js"));
+
+        let section = selection.prompt_section();
+        assert!(section.contains("외 "));
+        assert!(tokens.count(&section) <= 1000 / DROPPED_LIST_SHARE);
+        assert!(tokens.count(&selection.content) + tokens.count(&section) <= 1000);
+    }
+
+    #[test]
+    fn oversized_first_file_is_truncated() {
+        let diff = diff(&[("src/index.js", 500), ("src/other.js", 1)]);
+        let tokens = TokenCounter::for_model("gpt-4o");
+        let ranked = RelevanceScorer::new(&[]).rank(&diff);
+
+        let selection = Prioritized::select(&diff, ranked, 300, tokens);
+        assert_eq!(selection.partial.as_ref().map(|file| file.path.as_str()), Some("src/index.js"));
+        assert!(selection.content.contains("토큰 제한으로 나머지 내용 생략"));
+        assert!(!selection.is_complete());
+    }
+
+    #[test]
+    fn everything_fits() {
+        let diff = diff(&[("src/a.js", 1), ("src/b.js", 1)]);
+        let ranked = RelevanceScorer::new(&[]).rank(&diff);
+        let selection = Prioritized::select(&diff, ranked, 10_000, TokenCounter::for_model("gpt-4o"));
+        assert_eq!(selection.included, 2);
+        assert!(selection.is_complete());
+    }
+}
@@ -0,0 +1,524 @@
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset};
+use clap::ValueEnum;
+use git2::{Commit, Delta, DiffFindOptions, DiffLineType, DiffOptions, ErrorCode, Patch, Repository, Sort, Time, Tree};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use crate::diff::{Diff, DiffLine, FileDiff, FileStatus, Hunk, LineKind, path_from_bytes};
+use crate::filter::{ExcludedFile, DiffFilter};
+use crate::series::CommitInfo;
+use crate::source::DiffSource;
+use crate::tags::TagInfo;
+
+/// 저장소를 읽는 방식
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum BackendKind {
+    /// libgit2로 프로세스 안에서 직접 읽음
+    Libgit2,
+    /// git 명령 실행 (partial clone 등 libgit2가 지원하지 않는 저장소용)
+    Cli,
+}
+
+/// diff 생성과 커밋 조회에 사용하는 git 저장소 인터페이스
+///
+/// 구현은 tokio 런타임을 막지 않아야 함 (블로킹 작업은 별도 스레드나 비동기 프로세스로 실행)
+#[async_trait]
+pub trait RepoBackend: Send + Sync {
+    /// 로그에 표시할 백엔드 이름
+    fn name(&self) -> &'static str;
+
+    /// 리비전이 커밋을 가리키는지 여부
+    async fn resolves_to_commit(&self, rev: &str) -> Result<bool>;
+
+    /// 제외 규칙을 적용한 diff와 제외된 파일 목록
+    async fn diff(&self, source: &DiffSource, rules: &DiffFilter) -> Result<(Diff, Vec<ExcludedFile>)>;
+
+    /// `from..to` 범위의 커밋 목록 (오래된 순, 병합 커밋 제외)
+    ///
+    /// 병합 커밋의 변경은 병합된 개별 커밋에 이미 포함되어 있으므로 제외함
+    async fn list_commits(&self, from: &str, to: &str) -> Result<Vec<CommitInfo>>;
+
+    /// 커밋을 가리키는 태그 목록 (순서 없음, 트리나 blob을 가리키는 태그는 제외)
+    async fn list_tags(&self) -> Result<Vec<TagInfo>>;
+
+    /// 두 리비전의 공통 조상 커밋 SHA
+    async fn merge_base(&self, a: &str, b: &str) -> Result<String>;
+
+    /// 리비전 시점의 파일 내용 (그 시점에 파일이 없으면 None)
+    async fn read_file(&self, rev: &str, path: &Path) -> Result<Option<Vec<u8>>>;
+}
+
+/// 선택한 방식으로 저장소를 엶
+pub fn open_backend(kind: BackendKind, path: &Path) -> Result<Box<dyn RepoBackend>> {
+    match kind {
+        BackendKind::Libgit2 => {
+            // 저장소가 아니면 첫 diff 전에 알 수 있도록 미리 열어봄
+            Repository::discover(path).with_context(|| format!("git 저장소를 열 수 없습니다: {}", path.display()))?;
+            Ok(Box::new(Git2Backend { path: path.to_path_buf() }))
+        }
+        BackendKind::Cli => Ok(Box::new(GitCliBackend { path: path.to_path_buf() })),
+    }
+}
+
+/// libgit2 백엔드
+///
+/// `Repository`는 스레드 간에 공유할 수 없으므로 작업마다 blocking 스레드에서 저장소를 새로 엶
+pub struct Git2Backend {
+    path: PathBuf,
+}
+
+impl Git2Backend {
+    async fn with_repo<T: Send + 'static>(&self, work: impl FnOnce(&Repository) -> Result<T> + Send + 'static) -> Result<T> {
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || {
+            let repo = Repository::discover(&path).with_context(|| format!("git 저장소를 열 수 없습니다: {}", path.display()))?;
+            work(&repo)
+        })
+        .await?
+    }
+}
+
+#[async_trait]
+impl RepoBackend for Git2Backend {
+    fn name(&self) -> &'static str {
+        "libgit2"
+    }
+
+    async fn resolves_to_commit(&self, rev: &str) -> Result<bool> {
+        let rev = rev.to_string();
+        self.with_repo(move |repo| Ok(find_commit(repo, &rev).is_ok())).await
+    }
+
+    async fn diff(&self, source: &DiffSource, rules: &DiffFilter) -> Result<(Diff, Vec<ExcludedFile>)> {
+        let (source, rules) = (source.clone(), rules.clone());
+        self.with_repo(move |repo| git2_diff(repo, &source, &rules)).await
+    }
+
+    async fn list_commits(&self, from: &str, to: &str) -> Result<Vec<CommitInfo>> {
+        let (from, to) = (from.to_string(), to.to_string());
+        self.with_repo(move |repo| {
+            let mut walk = repo.revwalk()?;
+            walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+            walk.push(find_commit(repo, &to)?.id())?;
+            walk.hide(find_commit(repo, &from)?.id())?;
+
+            let mut commits = Vec::new();
+            for oid in walk {
+                let commit = repo.find_commit(oid?)?;
+                if commit.parent_count() > 1 {
+                    continue;
+                }
+                let author = commit.author();
+                commits.push(CommitInfo {
+                    sha: commit.id().to_string(),
+                    author: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+                    date: format_time(author.when(), "%Y-%m-%d"),
+                    subject: commit.summary_bytes().map(String::from_utf8_lossy).unwrap_or_default().into_owned(),
+                });
+            }
+            Ok(commits)
+        })
+        .await
+    }
+
+    async fn list_tags(&self) -> Result<Vec<TagInfo>> {
+        self.with_repo(|repo| {
+            let mut tags = Vec::new();
+            // UTF-8이 아닌 태그 이름은 리비전으로 지정할 수 없으므로 제외
+            for name in repo.tag_names(None)?.iter().flatten() {
+                let Ok(commit) = find_commit(repo, &format!("refs/tags/{}", name)) else {
+                    continue;
+                };
+                tags.push(TagInfo {
+                    name: name.to_string(),
+                    sha: commit.id().to_string(),
+                    date: format_time(commit.committer().when(), "%Y-%m-%d"),
+                });
+            }
+            Ok(tags)
+        })
+        .await
+    }
+
+    async fn merge_base(&self, a: &str, b: &str) -> Result<String> {
+        let (a, b) = (a.to_string(), b.to_string());
+        self.with_repo(move |repo| Ok(repo.merge_base(find_commit(repo, &a)?.id(), find_commit(repo, &b)?.id())?.to_string()))
+            .await
+    }
+
+    async fn read_file(&self, rev: &str, path: &Path) -> Result<Option<Vec<u8>>> {
+        let (rev, path) = (rev.to_string(), path.to_path_buf());
+        self.with_repo(move |repo| {
+            let entry = match find_commit(repo, &rev)?.tree()?.get_path(&path) {
+                Ok(entry) => entry,
+                Err(error) if error.code() == ErrorCode::NotFound => return Ok(None),
+                Err(error) => return Err(error.into()),
+            };
+            let blob = entry.to_object(repo)?.peel_to_blob()?;
+            Ok(Some(blob.content().to_vec()))
+        })
+        .await
+    }
+}
+
+fn find_commit<'r>(repo: &'r Repository, rev: &str) -> Result<Commit<'r>> {
+    Ok(repo.revparse_single(rev)?.peel_to_commit()?)
+}
+
+/// HEAD의 트리 (아직 커밋이 없으면 None)
+fn head_tree(repo: &Repository) -> Result<Option<Tree<'_>>> {
+    match repo.head() {
+        Ok(head) => Ok(Some(head.peel_to_tree()?)),
+        Err(error) if error.code() == ErrorCode::UnbornBranch => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// `git diff`/`git show`와 같은 비교를 libgit2로 수행 (이름 변경과 복사 감지 포함)
+fn git2_diff(repo: &Repository, source: &DiffSource, rules: &DiffFilter) -> Result<(Diff, Vec<ExcludedFile>)> {
+    let mut options = DiffOptions::new();
+    let mut preamble = String::new();
+    let mut diff = match source {
+        DiffSource::Range { from, to, merge_base } => {
+            let to = find_commit(repo, to)?;
+            let from = match merge_base {
+                true => {
+                    let from = find_commit(repo, from)?;
+                    repo.find_commit(repo.merge_base(from.id(), to.id())?)?
+                }
+                false => find_commit(repo, from)?,
+            };
+            repo.diff_tree_to_tree(Some(&from.tree()?), Some(&to.tree()?), Some(&mut options))?
+        }
+        DiffSource::Commit(rev) => {
+            // 병합 커밋은 첫 번째 부모와 비교
+            let commit = find_commit(repo, rev)?;
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            preamble = commit_preamble(&commit);
+            repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), Some(&mut options))?
+        }
+        DiffSource::Staged => repo.diff_tree_to_index(head_tree(repo)?.as_ref(), None, Some(&mut options))?,
+        DiffSource::Worktree => repo.diff_tree_to_workdir_with_index(head_tree(repo)?.as_ref(), Some(&mut options))?,
+    };
+    diff.find_similar(Some(DiffFindOptions::new().renames(true).copies(true)))?;
+
+    // 제외 보고서를 위한 전체 변경 파일 목록과, 제외 대상이 아닌 파일의 패치
+    let mut changed_paths = Vec::new();
+    let mut files = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let old_path = delta.old_file().path_bytes().map(path_from_bytes);
+        let new_path = delta.new_file().path_bytes().map(path_from_bytes);
+        if let Some(path) = new_path.clone().or_else(|| old_path.clone()) {
+            changed_paths.push(path);
+        }
+        // 잠금 파일처럼 큰 제외 대상 파일은 패치를 만들지 않음
+        if [&old_path, &new_path].into_iter().flatten().any(|path| rules.exclusion_reason(path).is_some()) {
+            continue;
+        }
+
+        let status = match delta.status() {
+            Delta::Added | Delta::Untracked => FileStatus::Added,
+            Delta::Deleted => FileStatus::Deleted,
+            Delta::Renamed => FileStatus::Renamed,
+            Delta::Copied => FileStatus::Copied,
+            _ => FileStatus::Modified,
+        };
+        let mut file = FileDiff {
+            old_path: old_path.filter(|_| status != FileStatus::Added),
+            new_path: new_path.filter(|_| status != FileStatus::Deleted),
+            status,
+            binary: delta.flags().is_binary(),
+            header: Vec::new(),
+            hunks: Vec::new(),
+        };
+        if let Some(mut patch) = Patch::from_diff(&diff, index)? {
+            read_patch(&mut patch, &mut file)?;
+        }
+        files.push(file);
+    }
+
+    let mut diff = Diff { preamble, files };
+    let excluded = rules.apply(&mut diff, &changed_paths);
+    Ok((diff, excluded))
+}
+
+/// 패치의 헤더와 hunk를 구조화된 diff로 옮김
+fn read_patch(patch: &mut Patch<'_>, file: &mut FileDiff) -> Result<()> {
+    file.binary |= patch.delta().flags().is_binary();
+    let mut hunk: Option<Hunk> = None;
+    patch.print(&mut |_, _, line| {
+        let content = String::from_utf8_lossy(line.content());
+        let content = content.strip_suffix('\n').unwrap_or(&content);
+        let content = content.strip_suffix('\r').unwrap_or(content);
+
+        let kind = match line.origin_value() {
+            DiffLineType::FileHeader | DiffLineType::Binary => {
+                file.header.extend(content.lines().map(str::to_string));
+                if line.origin_value() == DiffLineType::Binary {
+                    file.binary = true;
+                }
+                return true;
+            }
+            DiffLineType::HunkHeader => {
+                if let Some(hunk) = hunk.take() {
+                    file.hunks.push(hunk);
+                }
+                let (old_lines, new_lines) = (patch_hunk_lines(content, '-'), patch_hunk_lines(content, '+'));
+                hunk = Some(Hunk { header: content.to_string(), old_lines, new_lines, lines: Vec::new() });
+                return true;
+            }
+            DiffLineType::Addition => LineKind::Added,
+            DiffLineType::Deletion => LineKind::Removed,
+            DiffLineType::Context => LineKind::Context,
+            DiffLineType::ContextEOFNL | DiffLineType::AddEOFNL | DiffLineType::DeleteEOFNL => LineKind::NoNewline,
+        };
+        let content = match kind {
+            // libgit2는 "\n\\ No newline at end of file\n" 전체를 내용으로 전달함
+            LineKind::NoNewline => content.trim_start_matches('\n').trim_start_matches('\\').to_string(),
+            _ => content.to_string(),
+        };
+        if let Some(hunk) = hunk.as_mut() {
+            hunk.lines.push(DiffLine { kind, content, new_lineno: line.new_lineno() });
+        }
+        true
+    })?;
+    if let Some(hunk) = hunk {
+        file.hunks.push(hunk);
+    }
+    Ok(())
+}
+
+/// hunk 헤더(`@@ -a,b +c,d @@`)의 이전/새 줄 수
+fn patch_hunk_lines(header: &str, sign: char) -> u32 {
+    header
+        .split(' ')
+        .find_map(|part| part.strip_prefix(sign))
+        .map(|range| match range.split_once(',') {
+            Some((_, count)) => count.parse().unwrap_or(0),
+            None => 1,
+        })
+        .unwrap_or(0)
+}
+
+/// `git show --format=fuller`와 같은 커밋 정보
+fn commit_preamble(commit: &Commit<'_>) -> String {
+    let person = |signature: &git2::Signature<'_>| {
+        format!(
+            "{} <{}>",
+            String::from_utf8_lossy(signature.name_bytes()),
+            String::from_utf8_lossy(signature.email_bytes())
+        )
+    };
+    let date = |signature: &git2::Signature<'_>| format_time(signature.when(), "%a %b %-d %H:%M:%S %Y %z");
+
+    let mut preamble = format!("commit {}\n", commit.id());
+    if commit.parent_count() > 1 {
+        let parents: Vec<String> = commit.parent_ids().map(|id| id.to_string()[..7].to_string()).collect();
+        preamble.push_str(&format!("Merge: {}\n", parents.join(" ")));
+    }
+    let (author, committer) = (commit.author(), commit.committer());
+    preamble.push_str(&format!("Author:     {}\n", person(&author)));
+    preamble.push_str(&format!("AuthorDate: {}\n", date(&author)));
+    preamble.push_str(&format!("Commit:     {}\n", person(&committer)));
+    preamble.push_str(&format!("CommitDate: {}\n\n", date(&committer)));
+    for line in String::from_utf8_lossy(commit.message_bytes()).trim_end().lines() {
+        preamble.push_str(&format!("    {}\n", line));
+    }
+    preamble.push('\n');
+    preamble
+}
+
+/// 커밋 시각을 작성자의 시간대로 표시
+fn format_time(time: Time, format: &str) -> String {
+    let offset = FixedOffset::east_opt(time.offset_minutes() * 60).unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+    DateTime::from_timestamp(time.seconds(), 0)
+        .map(|date| date.with_timezone(&offset).format(format).to_string())
+        .unwrap_or_default()
+}
+
+/// git 명령 백엔드 (비동기 프로세스로 실행)
+pub struct GitCliBackend {
+    path: PathBuf,
+}
+
+impl GitCliBackend {
+    /// git 명령을 실행하고 표준 출력을 반환
+    async fn run_git(&self, args: &[String]) -> Result<Vec<u8>> {
+        let output = Command::new("git").current_dir(&self.path).args(args).output().await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("Git {} 실행 실패: {}", args[0], stderr));
+        }
+
+        Ok(output.stdout)
+    }
+}
+
+#[async_trait]
+impl RepoBackend for GitCliBackend {
+    fn name(&self) -> &'static str {
+        "git"
+    }
+
+    async fn resolves_to_commit(&self, rev: &str) -> Result<bool> {
+        let status = Command::new("git")
+            .current_dir(&self.path)
+            .args(["rev-parse", "--verify", "--quiet", "--end-of-options", &format!("{}^{{commit}}", rev)])
+            .output()
+            .await?
+            .status;
+        Ok(status.success())
+    }
+
+    /// 제외 규칙을 git pathspec으로 적용해 diff를 생성하고, 같은 규칙으로 후처리
+    async fn diff(&self, source: &DiffSource, rules: &DiffFilter) -> Result<(Diff, Vec<ExcludedFile>)> {
+        let (base_args, revisions) = source.git_args();
+        let to_strings = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        // 제외 보고서를 위해 pathspec 없이 변경 파일 목록 조회
+        let mut name_args = base_args.clone();
+        name_args.extend(to_strings(&["--name-only", "-z", "--format=", "--end-of-options"]));
+        name_args.extend(revisions.iter().cloned());
+        let changed_paths = parse_name_list(&self.run_git(&name_args).await?);
+
+        let mut diff_args = base_args;
+        diff_args.extend(to_strings(&["--no-color", "--no-ext-diff", "--find-renames", "--find-copies", "--end-of-options"]));
+        diff_args.extend(revisions);
+        diff_args.push("--".to_string());
+        diff_args.extend(rules.pathspecs());
+
+        let mut diff = Diff::parse(&self.run_git(&diff_args).await?);
+        let excluded = rules.apply(&mut diff, &changed_paths);
+
+        Ok((diff, excluded))
+    }
+
+    async fn list_commits(&self, from: &str, to: &str) -> Result<Vec<CommitInfo>> {
+        let args = [
+            "log",
+            "--reverse",
+            "--no-merges",
+            "--date=short",
+            "--format=%H%x1f%an%x1f%ad%x1f%s",
+            "--end-of-options",
+            &format!("{}..{}", from, to),
+        ];
+        let output = self.run_git(&args.map(str::to_string)).await?;
+
+        let commits = String::from_utf8_lossy(&output)
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, '\x1f');
+                Some(CommitInfo {
+                    sha: fields.next()?.to_string(),
+                    author: fields.next()?.to_string(),
+                    date: fields.next()?.to_string(),
+                    subject: fields.next().unwrap_or_default().to_string(),
+                })
+            })
+            .collect();
+        Ok(commits)
+    }
+
+    async fn list_tags(&self) -> Result<Vec<TagInfo>> {
+        // 주석 태그는 `*` 필드에 태그가 가리키는 객체 정보가 있음
+        let format = "%(refname:strip=2)%1f%(objecttype)%1f%(objectname)%1f%(committerdate:short)%1f\
+                      %(*objecttype)%1f%(*objectname)%1f%(*committerdate:short)";
+        let args = ["for-each-ref", &format!("--format={}", format), "refs/tags"];
+        let output = self.run_git(&args.map(str::to_string)).await?;
+
+        let tags = String::from_utf8_lossy(&output)
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\x1f').collect();
+                let [name, kind, sha, date, peeled_kind, peeled_sha, peeled_date] = fields[..] else {
+                    return None;
+                };
+                let (sha, date) = match (kind, peeled_kind) {
+                    ("commit", _) => (sha, date),
+                    (_, "commit") => (peeled_sha, peeled_date),
+                    _ => return None,
+                };
+                Some(TagInfo { name: name.to_string(), sha: sha.to_string(), date: date.to_string() })
+            })
+            .collect();
+        Ok(tags)
+    }
+
+    async fn merge_base(&self, a: &str, b: &str) -> Result<String> {
+        let output = self.run_git(&["merge-base", "--end-of-options", a, b].map(str::to_string)).await?;
+        Ok(String::from_utf8_lossy(&output).trim().to_string())
+    }
+
+    async fn read_file(&self, rev: &str, path: &Path) -> Result<Option<Vec<u8>>> {
+        // 경로가 없는 경우를 오류와 구분하기 위해 blob ID를 먼저 확인
+        // (UTF-8이 아닌 경로도 그대로 전달되도록 OsString으로 작성)
+        let mut object = OsString::from(rev);
+        object.push(":");
+        object.push(path);
+        let output = Command::new("git")
+            .current_dir(&self.path)
+            .args(["rev-parse", "--verify", "--quiet", "--end-of-options"])
+            .arg(&object)
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let blob = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(Some(self.run_git(&["cat-file".to_string(), "blob".to_string(), blob]).await?))
+    }
+}
+
+/// `-z` 옵션으로 출력된 파일 경로 목록 파싱
+fn parse_name_list(output: &[u8]) -> Vec<PathBuf> {
+    output
+        .split(|&b| b == 0)
+        .filter(|path| !path.is_empty())
+        .map(path_from_bytes)
+        .collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+    use std::fs;
+    use std::os::unix::ffi::OsStrExt;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[tokio::test]
+    async fn read_file_with_non_utf8_path() {
+        let dir = std::env::temp_dir().join(format!("git-diff-analyzer-repo-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        git(&dir, &["init", "-q"]);
+        let path = Path::new(OsStr::from_bytes(b"caf\xe9.js"));
+        fs::write(dir.join(path), "export const a = 1;\n").unwrap();
+        git(&dir, &["add", "."]);
+        git(&dir, &["commit", "-q", "-m", "init"]);
+
+        for kind in [BackendKind::Libgit2, BackendKind::Cli] {
+            let repo = open_backend(kind, &dir).unwrap();
+            assert_eq!(repo.read_file("HEAD", path).await.unwrap().as_deref(), Some(&b"export const a = 1;\n"[..]));
+            assert_eq!(repo.read_file("HEAD", Path::new("cafe.js")).await.unwrap(), None);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
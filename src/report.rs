@@ -0,0 +1,158 @@
+use anyhow::Result;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::analyzer::Analysis;
+use crate::api_surface::ApiSurfaceDelta;
+use crate::compat::CompatReport;
+use crate::diff::Diff;
+use crate::filter::{ExcludedFile, format_excluded_report};
+use crate::findings::FindingsReport;
+use crate::provider::RecordedPrompt;
+use crate::source::DiffSource;
+use crate::stats::DiffStats;
+use crate::tokenizer::TokenCounter;
+
+/// 분석 결과 파일 저장
+///
+/// 모든 파일은 `<기준 경로>_<종류>` 이름으로 저장됨 (예: `reports/my-project_v1.0.0_v1.1.0_summary.md`).
+/// 저장 메서드는 출력 없이 저장한 파일 경로만 반환
+pub struct ReportWriter {
+    base: PathBuf,
+}
+
+impl ReportWriter {
+    /// `<출력 디렉토리>/<프로젝트>_<분석 대상>` 기준으로 저장 (출력 디렉토리가 없으면 생성)
+    pub fn new(output_dir: &Path, project: &str, source: &DiffSource) -> Result<Self> {
+        fs::create_dir_all(output_dir)?;
+        Ok(Self::with_base(output_dir.join(format!("{}_{}", project, source.report_label()))))
+    }
+
+    /// 기준 경로를 직접 지정 (커밋 시리즈의 커밋별 보고서 등)
+    pub fn with_base(base: PathBuf) -> Self {
+        Self { base }
+    }
+
+    /// `<기준 경로>_<suffix>` 경로
+    pub fn path(&self, suffix: &str) -> PathBuf {
+        let mut name = OsString::from(self.base.as_os_str());
+        name.push("_");
+        name.push(suffix);
+        PathBuf::from(name)
+    }
+
+    pub fn diff_path(&self) -> PathBuf {
+        self.path("diff.txt")
+    }
+
+    pub fn summary_path(&self) -> PathBuf {
+        self.path("summary.md")
+    }
+
+    pub fn findings_path(&self) -> PathBuf {
+        self.path("findings.json")
+    }
+
+    pub fn stats_path(&self) -> PathBuf {
+        self.path("stats.csv")
+    }
+
+    /// 커밋 시리즈 모드의 커밋별 보고서 디렉토리
+    pub fn commits_dir(&self) -> PathBuf {
+        self.path("commits")
+    }
+
+    pub fn write_diff(&self, diff: &Diff) -> Result<PathBuf> {
+        write(self.diff_path(), diff.render())
+    }
+
+    pub fn write_excluded(&self, excluded: &[ExcludedFile]) -> Result<PathBuf> {
+        write(self.path("excluded.md"), format_excluded_report(excluded))
+    }
+
+    pub fn write_summary(&self, summary: &str) -> Result<PathBuf> {
+        write(self.summary_path(), summary)
+    }
+
+    pub fn write_findings(&self, findings: &FindingsReport) -> Result<PathBuf> {
+        write(self.findings_path(), serde_json::to_string_pretty(findings)?)
+    }
+
+    pub fn write_stats(&self, stats: &DiffStats) -> Result<PathBuf> {
+        let path = self.stats_path();
+        stats.write_csv(&path)?;
+        Ok(path)
+    }
+
+    /// 커밋 시리즈 모드의 인덱스 보고서
+    pub fn write_index(&self, index: &str) -> Result<PathBuf> {
+        write(self.path("index.md"), index)
+    }
+
+    /// dry-run으로 기록한 프롬프트를 원문 그대로 저장하고, 토큰 수 요약 파일 작성
+    ///
+    /// 프롬프트 디렉토리와 요약 파일 경로를 반환
+    pub fn write_dry_run_prompts(&self, records: &[RecordedPrompt]) -> Result<(PathBuf, PathBuf)> {
+        // 이전 실행의 프롬프트가 섞이지 않도록 새로 만듦
+        let prompt_dir = self.path("prompts");
+        if prompt_dir.exists() {
+            fs::remove_dir_all(&prompt_dir)?;
+        }
+        fs::create_dir_all(&prompt_dir)?;
+        let prompt_dir_name = prompt_dir.file_name().unwrap_or_default().to_string_lossy();
+
+        let mut report = format!("# Dry-run 프롬프트 ({}개)\n\n", records.len());
+        report.push_str("| # | 모델 | 프롬프트 토큰 | 최대 응답 토큰 | 파일 |\n|---|---|---|---|---|\n");
+        let mut total_tokens = 0;
+        for (index, record) in records.iter().enumerate() {
+            let filename = format!("{:03}.txt", index + 1);
+            fs::write(prompt_dir.join(&filename), &record.prompt)?;
+
+            let tokens = TokenCounter::for_model(&record.model);
+            let prompt_tokens = tokens.count(&record.prompt);
+            total_tokens += prompt_tokens + record.max_tokens as usize;
+            report.push_str(&format!(
+                "| {} | {} | {} ({}) | {} | [{}]({}/{}) |\n",
+                index + 1,
+                record.model,
+                prompt_tokens,
+                tokens.label(),
+                record.max_tokens,
+                filename,
+                prompt_dir_name,
+                filename
+            ));
+        }
+        report.push_str(&format!("\n최대 총 토큰 (프롬프트 + 응답): {}\n", total_tokens));
+
+        let report_path = write(self.path("dry_run.md"), report)?;
+        Ok((prompt_dir, report_path))
+    }
+}
+
+/// 최종 요약 보고서 작성
+///
+/// 파일별 변경 통계는 모델 표시 바로 아래(모델 응답보다 앞)에 넣고,
+/// 정적 검사 결과는 모델 응답과 별도로 보고서 끝에 그대로 첨부
+pub fn compose_summary(
+    analysis: &Analysis,
+    stats: &DiffStats,
+    api_delta: Option<&ApiSurfaceDelta>,
+    compat: Option<&CompatReport>,
+) -> String {
+    let (header, report) = analysis.markdown.split_once("\n\n").unwrap_or((&analysis.markdown, ""));
+    let mut summary = format!("{}\n\n{}\n\n{}", header, stats.markdown().trim_end(), report);
+    if let Some(delta) = api_delta {
+        summary = format!("{}\n\n{}", summary.trim_end(), delta.markdown());
+    }
+    if let Some(report) = compat {
+        summary = format!("{}\n\n{}", summary.trim_end(), report.markdown());
+    }
+    summary
+}
+
+fn write(path: PathBuf, contents: impl AsRef<[u8]>) -> Result<PathBuf> {
+    fs::write(&path, contents)?;
+    Ok(path)
+}
@@ -0,0 +1,267 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::DateTime;
+use log::warn;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::{Duration, SystemTime};
+
+use crate::provider::{ApiError, CompletionRequest, DeltaSink, LlmProvider};
+
+/// 첫 재시도 대기 시간 (이후 시도마다 두 배)
+const BASE_DELAY: Duration = Duration::from_secs(2);
+/// 지수 백오프 대기 시간 상한
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// 서버가 알려준 대기 시간의 상한 (잘못된 헤더로 오래 멈추지 않도록)
+const MAX_SERVER_DELAY: Duration = Duration::from_secs(300);
+/// 로그에 출력할 오류 응답 본문 최대 길이
+const MAX_LOGGED_BODY_CHARS: usize = 200;
+
+/// 재시도 정책
+pub struct RetryPolicy {
+    /// 첫 요청을 포함한 최대 시도 횟수
+    pub max_attempts: u32,
+}
+
+impl RetryPolicy {
+    /// `attempt`번째 실패 후의 백오프 대기 시간 (동시 요청이 같은 시각에 몰리지 않도록 최대 25% 지터 추가)
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt - 1)).min(MAX_BACKOFF);
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        delay + delay.mul_f64(f64::from(nanos % 250) / 1000.0)
+    }
+}
+
+/// 일시적인 실패(429, 5xx, 연결 실패, 시간 초과)를 재시도하는 제공자 래퍼
+pub struct RetryingProvider {
+    inner: Box<dyn LlmProvider>,
+    policy: RetryPolicy,
+}
+
+impl RetryingProvider {
+    pub fn new(inner: Box<dyn LlmProvider>, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    /// `on_delta`가 있으면 스트리밍으로 요청 (응답 일부를 이미 전달한 뒤의 실패는 재시도하지 않음)
+    async fn complete_with_retry(&self, request: &CompletionRequest, mut on_delta: Option<DeltaSink<'_>>) -> Result<String> {
+        let max_attempts = self.policy.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            let mut streamed = false;
+            let result = match on_delta.as_deref_mut() {
+                Some(on_delta) => {
+                    let mut forward = |delta: &str| {
+                        streamed = true;
+                        on_delta(delta);
+                    };
+                    self.inner.complete_stream(request, &mut forward).await
+                }
+                None => self.inner.complete(request).await,
+            };
+            let error = match result {
+                Ok(content) => return Ok(content),
+                Err(error) => error,
+            };
+            if streamed || !is_retryable(&error) {
+                return Err(error);
+            }
+            if attempt >= max_attempts {
+                warn!("{} API 요청이 {}번 모두 실패했습니다.", self.name(), max_attempts);
+                return Err(error);
+            }
+
+            let (delay, reason) = match server_delay(&error) {
+                Some(delay) => (delay.min(MAX_SERVER_DELAY), "서버 요청"),
+                None => (self.policy.backoff(attempt), "백오프"),
+            };
+            warn!(
+                "{} API 요청 실패 (시도 {}/{}): {} → {:.1}초 후 재시도합니다 ({})",
+                self.name(),
+                attempt,
+                max_attempts,
+                summarize_error(&error),
+                delay.as_secs_f64(),
+                reason
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for RetryingProvider {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<String> {
+        self.complete_with_retry(request, None).await
+    }
+
+    async fn complete_stream(&self, request: &CompletionRequest, on_delta: DeltaSink<'_>) -> Result<String> {
+        self.complete_with_retry(request, Some(on_delta)).await
+    }
+}
+
+/// 다시 시도하면 성공할 수 있는 오류인지 여부
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    if let Some(api_error) = error.downcast_ref::<ApiError>() {
+        return api_error.is_retryable();
+    }
+    error
+        .downcast_ref::<reqwest::Error>()
+        .is_some_and(|error| error.is_timeout() || error.is_connect())
+}
+
+fn server_delay(error: &anyhow::Error) -> Option<Duration> {
+    error.downcast_ref::<ApiError>().and_then(|api_error| api_error.retry_after)
+}
+
+/// 로그 한 줄에 들어가도록 줄인 오류 설명
+fn summarize_error(error: &anyhow::Error) -> String {
+    match error.downcast_ref::<ApiError>() {
+        Some(api_error) => {
+            let body: String = api_error.body.split_whitespace().collect::<Vec<_>>().join(" ");
+            let mut summary: String = body.chars().take(MAX_LOGGED_BODY_CHARS).collect();
+            if body.chars().count() > MAX_LOGGED_BODY_CHARS {
+                summary.push_str("...");
+            }
+            format!("상태 {} {}", api_error.status, summary)
+        }
+        None => error.to_string(),
+    }
+}
+
+/// 응답 헤더에서 다음 요청까지 기다려야 하는 시간 추출 (None이면 지수 백오프)
+///
+/// `Retry-After`(초 또는 HTTP 날짜)를 우선 사용하고, 없으면 429 응답에서만 OpenAI의
+/// `x-ratelimit-reset-requests`/`x-ratelimit-reset-tokens` 중 남은 양(`x-ratelimit-remaining-*`)이
+/// 0인 한도의 초기화 시간을 사용함. 5xx 등 한도와 관계없는 실패는 한도 초기화를 기다리지 않음
+pub fn retry_delay_from_headers(status: u16, headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok()).map(str::trim);
+
+    if let Some(value) = header(RETRY_AFTER.as_str()) {
+        return parse_retry_after(value, SystemTime::now());
+    }
+    if status != 429 {
+        return None;
+    }
+
+    ["requests", "tokens"]
+        .into_iter()
+        .filter(|limit| header(&format!("x-ratelimit-remaining-{}", limit)) == Some("0"))
+        .filter_map(|limit| parse_reset_duration(header(&format!("x-ratelimit-reset-{}", limit))?))
+        .max()
+}
+
+/// `Retry-After` 값 파싱 (`120`처럼 초, 또는 `Wed, 21 Oct 2015 07:28:00 GMT`처럼 HTTP 날짜)
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    if let Ok(seconds) = value.parse::<f64>() {
+        return server_seconds(seconds);
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let now = now.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+    let seconds = date.timestamp() as f64 + f64::from(date.timestamp_subsec_millis()) / 1000.0 - now.as_secs_f64();
+    server_seconds(seconds)
+}
+
+/// `6m0s`, `1.5s`, `20ms` 형식의 초기화 시간 파싱
+fn parse_reset_duration(value: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let number_end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        let number: f64 = rest[..number_end].parse().ok()?;
+        rest = &rest[number_end..];
+
+        let (unit_seconds, unit_len) = if rest.starts_with("ms") {
+            (0.001, 2)
+        } else if rest.starts_with('h') {
+            (3600.0, 1)
+        } else if rest.starts_with('m') {
+            (60.0, 1)
+        } else if rest.starts_with('s') {
+            (1.0, 1)
+        } else {
+            return None;
+        };
+        total += number * unit_seconds;
+        rest = &rest[unit_len..];
+    }
+    (total > 0.0).then(|| server_seconds(total)).flatten()
+}
+
+/// 서버가 알려준 초 단위 대기 시간을 상한 안으로 변환 (`inf`, `NaN` 등은 무시)
+fn server_seconds(seconds: f64) -> Option<Duration> {
+    if !seconds.is_finite() {
+        return None;
+    }
+    Duration::try_from_secs_f64(seconds.clamp(0.0, MAX_SERVER_DELAY.as_secs_f64())).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn retry_after_seconds() {
+        assert_eq!(retry_delay_from_headers(503, &headers(&[("retry-after", "1.5")])), Some(Duration::from_millis(1500)));
+        assert_eq!(retry_delay_from_headers(429, &headers(&[("retry-after", "-3")])), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn retry_after_http_date() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_445_412_460);
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now), Some(Duration::from_secs(20)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("Thu, 22 Oct 2015 07:28:00 GMT", now), Some(MAX_SERVER_DELAY));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn retry_after_out_of_range_does_not_panic() {
+        assert_eq!(retry_delay_from_headers(429, &headers(&[("retry-after", "inf")])), None);
+        assert_eq!(retry_delay_from_headers(429, &headers(&[("retry-after", "NaN")])), None);
+        assert_eq!(retry_delay_from_headers(429, &headers(&[("retry-after", "1e30")])), Some(MAX_SERVER_DELAY));
+    }
+
+    #[test]
+    fn reset_durations() {
+        assert_eq!(parse_reset_duration("2m30s"), Some(Duration::from_secs(150)));
+        assert_eq!(parse_reset_duration("6m0s"), Some(MAX_SERVER_DELAY));
+        assert_eq!(parse_reset_duration("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(parse_reset_duration("99999999999999999999h"), Some(MAX_SERVER_DELAY));
+        assert_eq!(parse_reset_duration("0s"), None);
+        assert_eq!(parse_reset_duration("-1s"), None);
+        assert_eq!(parse_reset_duration("5x"), None);
+    }
+
+    #[test]
+    fn rate_limit_reset_only_for_exhausted_429() {
+        let limits = headers(&[
+            ("x-ratelimit-reset-requests", "2s"),
+            ("x-ratelimit-remaining-requests", "0"),
+            ("x-ratelimit-reset-tokens", "4m0s"),
+            ("x-ratelimit-remaining-tokens", "100"),
+        ]);
+        assert_eq!(retry_delay_from_headers(429, &limits), Some(Duration::from_secs(2)));
+        // 5xx는 한도 초기화 시간과 관계없이 백오프
+        assert_eq!(retry_delay_from_headers(500, &limits), None);
+
+        let not_exhausted = headers(&[("x-ratelimit-reset-tokens", "4m0s"), ("x-ratelimit-remaining-tokens", "100")]);
+        assert_eq!(retry_delay_from_headers(429, &not_exhausted), None);
+    }
+}
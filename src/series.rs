@@ -0,0 +1,213 @@
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+use log::{info, warn};
+use std::fs;
+use std::path::Path;
+
+use crate::analyzer::Analyzer;
+use crate::api_surface;
+use crate::compat::CompatScanner;
+use crate::filter::DiffFilter;
+use crate::findings::Risk;
+use crate::repo::RepoBackend;
+use crate::report::{ReportWriter, compose_summary};
+use crate::source::{DiffSource, get_diff};
+use crate::stats::DiffStats;
+
+/// 범위에 포함된 커밋 정보
+pub struct CommitInfo {
+    pub sha: String,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+}
+
+impl CommitInfo {
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(10)]
+    }
+}
+
+/// 커밋 하나의 분석 결과
+pub enum CommitOutcome {
+    Analyzed { risk: Risk, compat_findings: Option<usize>, summary_file: String },
+    /// 제외 규칙을 적용한 뒤 남은 변경이 없음
+    Empty,
+    Failed(String),
+}
+
+pub struct SeriesEntry {
+    pub commit: CommitInfo,
+    /// 변경 통계 (파일 수, 추가/삭제 줄 수)
+    pub stats: String,
+    pub outcome: CommitOutcome,
+}
+
+/// 범위의 커밋을 하나씩 분석하는 커밋 시리즈 모드
+pub struct SeriesRunner<'a> {
+    pub project: &'a str,
+    pub repo: &'a dyn RepoBackend,
+    pub rules: &'a DiffFilter,
+    pub analyzer: &'a Analyzer,
+    pub compat: Option<&'a CompatScanner>,
+    /// 커밋마다 공개 API 변경 요약을 만들지 여부
+    pub api_surface: bool,
+    /// 커밋별 보고서를 저장할 디렉토리
+    pub output_dir: &'a Path,
+}
+
+impl SeriesRunner<'_> {
+    /// 최대 `concurrency`개의 커밋을 동시에 분석 (결과는 커밋 순서 유지)
+    pub async fn run(&self, commits: Vec<CommitInfo>, concurrency: usize) -> Result<Vec<SeriesEntry>> {
+        fs::create_dir_all(self.output_dir)?;
+        let total = commits.len();
+
+        let entries = stream::iter(commits.into_iter().enumerate())
+            .map(|(index, commit)| async move {
+                info!("[{}/{}] {} {}", index + 1, total, commit.short_sha(), commit.subject);
+                let (stats, outcome) = match self.analyze_commit(index + 1, &commit).await {
+                    Ok(result) => result,
+                    Err(error) => {
+                        warn!("[{}/{}] {} 분석 실패: {}", index + 1, total, commit.short_sha(), error);
+                        (String::new(), CommitOutcome::Failed(error.to_string()))
+                    }
+                };
+                SeriesEntry { commit, stats, outcome }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        Ok(entries)
+    }
+
+    /// 보고서 파일 이름은 디렉토리에서 커밋 순서대로 정렬되도록 순번으로 시작함
+    async fn analyze_commit(&self, number: usize, commit: &CommitInfo) -> Result<(String, CommitOutcome)> {
+        let source = DiffSource::Commit(commit.sha.clone());
+        let (diff, _) = get_diff(self.repo, &source, self.rules).await?;
+        if diff.files.is_empty() {
+            return Ok((String::new(), CommitOutcome::Empty));
+        }
+
+        let compat_report = self.compat.map(|scanner| scanner.scan(&diff));
+        let api_delta = match self.api_surface {
+            true => api_surface::compare(self.repo, &source, &diff).await?,
+            false => None,
+        };
+        let static_sections: Vec<String> = [
+            api_delta.as_ref().map(|delta| delta.prompt_section()),
+            compat_report.as_ref().map(|report| report.prompt_section()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let static_findings = (!static_sections.is_empty()).then(|| static_sections.join("\n\n"));
+
+        let analysis = self
+            .analyzer
+            .analyze(source.target(self.project), &diff, static_findings.as_deref(), None)
+            .await?;
+        let risk = match &analysis.findings {
+            Some(findings) => findings.overall_risk,
+            None => Risk::from_report(&analysis.markdown),
+        };
+        let stats = DiffStats::new(&diff);
+        let summary = compose_summary(&analysis, &stats, api_delta.as_ref(), compat_report.as_ref());
+
+        let writer = ReportWriter::with_base(self.output_dir.join(format!("{:03}_{}", number, commit.short_sha())));
+        writer.write_diff(&diff)?;
+        if let Some(findings) = &analysis.findings {
+            writer.write_findings(findings)?;
+        }
+        let summary_path = writer.write_summary(&summary)?;
+        writer.write_stats(&stats)?;
+        // 인덱스에서는 디렉토리 이름 기준 상대 경로로 링크
+        let summary_file = summary_path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let outcome = CommitOutcome::Analyzed {
+            risk,
+            compat_findings: compat_report.map(|report| report.findings.len()),
+            summary_file,
+        };
+        Ok((diff.stats_line(), outcome))
+    }
+}
+
+/// 커밋별 보고서 링크와 리스크 집계를 담은 인덱스 보고서
+pub fn format_index(project: &str, range: &str, output_dir_name: &str, entries: &[SeriesEntry]) -> String {
+    let mut report = format!("# {} 커밋별 변경사항 분석 ({})\n\n", project, range);
+    report.push_str(&format!("- 커밋 수: {}개 (병합 커밋 제외)\n\n", entries.len()));
+
+    // 리스크 집계
+    let risks: Vec<Risk> = entries
+        .iter()
+        .filter_map(|entry| match entry.outcome {
+            CommitOutcome::Analyzed { risk, .. } => Some(risk),
+            _ => None,
+        })
+        .collect();
+    let empty = entries.iter().filter(|entry| matches!(entry.outcome, CommitOutcome::Empty)).count();
+    let failed = entries.iter().filter(|entry| matches!(entry.outcome, CommitOutcome::Failed(_))).count();
+
+    report.push_str("## 📊 리스크 요약\n\n| 리스크 | 커밋 수 |\n|---|---|\n");
+    for level in [Risk::High, Risk::Medium, Risk::Low, Risk::Unknown] {
+        report.push_str(&format!("| {} | {} |\n", level.label(), risks.iter().filter(|&&risk| risk == level).count()));
+    }
+    report.push_str(&format!("| 분석 대상 변경 없음 | {} |\n| 분석 실패 | {} |\n\n", empty, failed));
+
+    // 리스크가 높은 커밋 먼저
+    let mut high_risk: Vec<&SeriesEntry> = entries
+        .iter()
+        .filter(|entry| matches!(entry.outcome, CommitOutcome::Analyzed { risk: Risk::High | Risk::Medium, .. }))
+        .collect();
+    high_risk.sort_by_key(|entry| match entry.outcome {
+        CommitOutcome::Analyzed { risk, .. } => risk,
+        _ => Risk::Unknown,
+    });
+    if !high_risk.is_empty() {
+        report.push_str("## 🚨 확인이 필요한 커밋\n\n");
+        for entry in high_risk {
+            if let CommitOutcome::Analyzed { risk, summary_file, .. } = &entry.outcome {
+                report.push_str(&format!(
+                    "- {} [`{}`]({}/{}) {}\n",
+                    risk.label(),
+                    entry.commit.short_sha(),
+                    output_dir_name,
+                    summary_file,
+                    escape_cell(&entry.commit.subject)
+                ));
+            }
+        }
+        report.push('\n');
+    }
+
+    report.push_str("## 📝 커밋 목록\n\n| # | 커밋 | 제목 | 작성자 | 날짜 | 변경 | 리스크 | 정적 검사 |\n|---|---|---|---|---|---|---|---|\n");
+    for (index, entry) in entries.iter().enumerate() {
+        let commit = &entry.commit;
+        let (link, risk, compat) = match &entry.outcome {
+            CommitOutcome::Analyzed { risk, compat_findings, summary_file } => (
+                format!("[`{}`]({}/{})", commit.short_sha(), output_dir_name, summary_file),
+                risk.label().to_string(),
+                compat_findings.map(|count| format!("{}건", count)).unwrap_or_else(|| "-".to_string()),
+            ),
+            CommitOutcome::Empty => (format!("`{}`", commit.short_sha()), "변경 없음 (제외 규칙)".to_string(), "-".to_string()),
+            CommitOutcome::Failed(error) => (format!("`{}`", commit.short_sha()), format!("분석 실패: {}", escape_cell(error)), "-".to_string()),
+        };
+        report.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            index + 1,
+            link,
+            escape_cell(&commit.subject),
+            escape_cell(&commit.author),
+            commit.date,
+            entry.stats,
+            risk,
+            compat
+        ));
+    }
+    report
+}
+
+/// 마크다운 표 셀 안에서 표를 깨뜨리는 문자 처리
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
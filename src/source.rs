@@ -0,0 +1,144 @@
+use anyhow::{Result, anyhow};
+use log::info;
+
+use crate::diff::Diff;
+use crate::filter::{ExcludedFile, DiffFilter};
+use crate::prompt::AnalysisTarget;
+use crate::repo::RepoBackend;
+
+/// 분석할 변경사항의 출처
+#[derive(Clone)]
+pub enum DiffSource {
+    /// 두 리비전 비교 (`merge_base`이면 `from...to`, 즉 공통 조상과 `to` 비교)
+    Range { from: String, to: String, merge_base: bool },
+    /// 단일 커밋
+    Commit(String),
+    /// 스테이징된 변경사항 (인덱스 ↔ HEAD)
+    Staged,
+    /// 커밋되지 않은 모든 변경사항 (작업 트리 ↔ HEAD)
+    Worktree,
+}
+
+impl DiffSource {
+    /// `A...B`(공통 조상 기준) 또는 `A..B` 형식의 범위 파싱
+    pub fn parse_range(spec: &str) -> Option<DiffSource> {
+        let (from, to, merge_base) = match spec.split_once("...") {
+            Some((from, to)) => (from, to, true),
+            None => {
+                let (from, to) = spec.split_once("..")?;
+                (from, to, false)
+            }
+        };
+
+        // git과 같이 생략된 쪽은 HEAD
+        let or_head = |rev: &str| if rev.is_empty() { "HEAD".to_string() } else { rev.to_string() };
+        Some(DiffSource::Range { from: or_head(from), to: or_head(to), merge_base })
+    }
+
+    /// 사람이 읽을 수 있는 대상 설명 (프로필의 `{{refs}}` 변수로도 사용)
+    pub fn description(&self) -> String {
+        match self {
+            DiffSource::Range { from, to, merge_base: false } => format!("{} → {}", from, to),
+            DiffSource::Range { from, to, merge_base: true } => format!("{} → {} (공통 조상 기준)", from, to),
+            DiffSource::Commit(commit) => format!("커밋 {}", commit),
+            DiffSource::Staged => "스테이징된 변경사항".to_string(),
+            DiffSource::Worktree => "커밋되지 않은 변경사항".to_string(),
+        }
+    }
+
+    /// 프로필의 `{{from_ref}}`, `{{to_ref}}` 변수 값
+    pub fn refs(&self) -> (String, String) {
+        match self {
+            DiffSource::Range { from, to, .. } => (from.clone(), to.clone()),
+            DiffSource::Commit(commit) => (commit.clone(), String::new()),
+            DiffSource::Staged => ("HEAD".to_string(), "index".to_string()),
+            DiffSource::Worktree => ("HEAD".to_string(), "worktree".to_string()),
+        }
+    }
+
+    /// 보고서 파일 이름에 들어가는 부분
+    pub fn report_label(&self) -> String {
+        match self {
+            DiffSource::Range { from, to, merge_base: false } => format!("{}_{}", sanitize(from), sanitize(to)),
+            DiffSource::Range { from, to, merge_base: true } => format!("{}...{}", sanitize(from), sanitize(to)),
+            DiffSource::Commit(commit) => format!("commit_{}", sanitize(commit)),
+            DiffSource::Staged => "staged".to_string(),
+            DiffSource::Worktree => "worktree".to_string(),
+        }
+    }
+
+    pub fn target<'a>(&'a self, project: &'a str) -> AnalysisTarget<'a> {
+        match self {
+            DiffSource::Range { from, to, merge_base } => AnalysisTarget::Range { project, from, to, merge_base: *merge_base },
+            DiffSource::Commit(commit) => AnalysisTarget::Commit { project, commit },
+            DiffSource::Staged => AnalysisTarget::Uncommitted { project, staged: true },
+            DiffSource::Worktree => AnalysisTarget::Uncommitted { project, staged: false },
+        }
+    }
+
+    pub fn empty_message(&self) -> &'static str {
+        match self {
+            DiffSource::Range { .. } => "두 리비전 간에 변경사항이 없습니다.",
+            DiffSource::Commit(_) => "해당 커밋에 변경사항이 없습니다.",
+            DiffSource::Staged => "스테이징된 변경사항이 없습니다.",
+            DiffSource::Worktree => "커밋되지 않은 변경사항이 없습니다.",
+        }
+    }
+
+    /// diff 생성에 사용할 git 하위 명령과 옵션, 비교할 리비전 (pathspec 제외)
+    ///
+    /// 리비전은 옵션으로 해석되지 않도록 `--end-of-options` 뒤에 붙여야 함.
+    /// 병합 커밋은 libgit2 백엔드와 같이 첫 번째 부모와 비교함
+    pub fn git_args(&self) -> (Vec<String>, Vec<String>) {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        match self {
+            DiffSource::Range { from, to, merge_base: false } => (args(&["diff"]), vec![from.clone(), to.clone()]),
+            DiffSource::Range { from, to, merge_base: true } => (args(&["diff"]), vec![format!("{}...{}", from, to)]),
+            DiffSource::Commit(commit) => {
+                (args(&["show", "--format=fuller", "--diff-merges=first-parent"]), vec![commit.clone()])
+            }
+            DiffSource::Staged => (args(&["diff", "--cached"]), Vec::new()),
+            DiffSource::Worktree => (args(&["diff"]), args(&["HEAD"])),
+        }
+    }
+
+    /// 비교할 리비전 목록 (존재 여부 확인용)
+    fn revisions(&self) -> Vec<&str> {
+        match self {
+            DiffSource::Range { from, to, .. } => vec![from, to],
+            DiffSource::Commit(commit) => vec![commit],
+            DiffSource::Staged => Vec::new(),
+            DiffSource::Worktree => vec!["HEAD"],
+        }
+    }
+}
+
+/// 파일 이름에 쓸 수 없거나 불편한 문자(`/`, `~`, `^` 등)를 `_`로 치환
+fn sanitize(rev: &str) -> String {
+    rev.chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect()
+}
+
+/// 리비전이 커밋을 가리키는지 확인하고, 없으면 이해하기 쉬운 오류 반환
+pub async fn verify_revisions(repo: &dyn RepoBackend, source: &DiffSource) -> Result<()> {
+    for rev in source.revisions() {
+        if !repo.resolves_to_commit(rev).await? {
+            return Err(anyhow!(
+                "리비전을 찾을 수 없습니다: {} (브랜치, 태그, 커밋 SHA, HEAD~N 등을 지정할 수 있습니다)",
+                rev
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub async fn get_diff(repo: &dyn RepoBackend, source: &DiffSource, rules: &DiffFilter) -> Result<(Diff, Vec<ExcludedFile>)> {
+    info!("{} git diff 생성 중... ({})", source.description(), repo.name());
+
+    let (diff, excluded) = repo.diff(source, rules).await?;
+
+    info!("제외 규칙에 따라 파일 {}개가 제외된 diff가 생성되었습니다.", excluded.len());
+
+    Ok((diff, excluded))
+}
@@ -0,0 +1,196 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::diff::{Diff, FileDiff, FileStatus};
+
+/// 보고서 표에 보여줄 최대 파일 수 (나머지는 CSV에만 기록)
+const MAX_TABLE_ROWS: usize = 100;
+
+/// 파일 하나의 변경 통계 (`git diff --numstat`과 같은 값, CSV 한 줄)
+#[derive(Serialize)]
+pub struct FileStat {
+    /// 변경 종류 코드 (A/D/M/R/C)
+    pub status: char,
+    /// 이름 변경/복사 전 경로 (그 외에는 빈 값)
+    pub old_path: String,
+    pub path: String,
+    /// 추가/삭제 줄 수 (바이너리 파일은 빈 값)
+    pub added: Option<usize>,
+    pub removed: Option<usize>,
+    /// 이름 변경/복사의 유사도 (%)
+    pub similarity: Option<u32>,
+}
+
+impl FileStat {
+    fn new(file: &FileDiff) -> Self {
+        let old_path = match file.status {
+            FileStatus::Renamed | FileStatus::Copied => file.old_path.as_ref().map(|path| path.display().to_string()),
+            _ => None,
+        };
+        let counts = |count: usize| (!file.binary).then_some(count);
+        Self {
+            status: file.status.code(),
+            old_path: old_path.unwrap_or_default(),
+            path: file.path().display().to_string(),
+            added: counts(file.added_lines()),
+            removed: counts(file.removed_lines()),
+            similarity: file.similarity(),
+        }
+    }
+
+    fn status_label(&self) -> String {
+        let label = match self.status {
+            'A' => "추가",
+            'D' => "삭제",
+            'R' => "이름 변경",
+            'C' => "복사",
+            _ => "수정",
+        };
+        match self.similarity {
+            Some(similarity) => format!("{} ({}%)", label, similarity),
+            None => label.to_string(),
+        }
+    }
+
+    fn path_label(&self) -> String {
+        match self.old_path.is_empty() {
+            true => format!("`{}`", self.path),
+            false => format!("`{}` → `{}`", self.old_path, self.path),
+        }
+    }
+}
+
+/// 분석 대상 파일별 변경 통계
+pub struct DiffStats {
+    pub files: Vec<FileStat>,
+}
+
+impl DiffStats {
+    pub fn new(diff: &Diff) -> Self {
+        Self { files: diff.files.iter().map(FileStat::new).collect() }
+    }
+
+    /// 요약 맨 앞에 넣는 파일별 변경 표
+    pub fn markdown(&self) -> String {
+        let added: usize = self.files.iter().filter_map(|file| file.added).sum();
+        let removed: usize = self.files.iter().filter_map(|file| file.removed).sum();
+        let mut report = format!("## 📊 파일별 변경 통계\n\n파일 {}개, +{} -{} 라인\n\n", self.files.len(), added, removed);
+
+        report.push_str("| 변경 | 파일 | 추가 | 삭제 |\n|---|---|---:|---:|\n");
+        let count = |count: Option<usize>| count.map_or_else(|| "바이너리".to_string(), |count| count.to_string());
+        for file in self.files.iter().take(MAX_TABLE_ROWS) {
+            report.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                file.status_label(),
+                file.path_label().replace('|', "\\|"),
+                count(file.added),
+                count(file.removed)
+            ));
+        }
+        if let Some(omitted) = self.files.len().checked_sub(MAX_TABLE_ROWS).filter(|&omitted| omitted > 0) {
+            report.push_str(&format!("\n외 {}개 파일은 CSV 파일을 참고해주세요.\n", omitted));
+        }
+        report
+    }
+
+    /// 파일별 변경 통계를 CSV로 저장 (머리글 행 포함)
+    pub fn write_csv(&self, path: &Path) -> Result<()> {
+        let mut writer = csv::Writer::from_path(path)?;
+        for file in &self.files {
+            writer.serialize(file)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/old.js b/src/new.js
+similarity index 90%
+rename from src/old.js
+rename to src/new.js
+--- a/src/old.js
++++ b/src/new.js
+@@ -1 +1 @@
+-a
++b
+diff --git a/src/base.js b/src/copy.js
+similarity index 75%
+copy from src/base.js
+copy to src/copy.js
+diff --git a/assets/logo.png b/assets/logo.png
+new file mode 100644
+index 0000000..1234567
+Binary files /dev/null and b/assets/logo.png differ
+diff --git a/src/main.js b/src/main.js
+--- a/src/main.js
++++ b/src/main.js
+@@ -1,2 +1,3 @@
+ keep
+-old
++new
++more
+";
+
+    #[test]
+    fn rename_copy_and_binary_rows() {
+        let stats = DiffStats::new(&Diff::parse(DIFF.as_bytes()));
+        let rows: Vec<_> = stats
+            .files
+            .iter()
+            .map(|file| (file.status, file.old_path.as_str(), file.path.as_str(), file.added, file.removed, file.similarity))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ('R', "src/old.js", "src/new.js", Some(1), Some(1), Some(90)),
+                ('C', "src/base.js", "src/copy.js", Some(0), Some(0), Some(75)),
+                ('A', "", "assets/logo.png", None, None, None),
+                ('M', "", "src/main.js", Some(2), Some(1), None),
+            ]
+        );
+
+        let markdown = stats.markdown();
+        assert!(markdown.contains("파일 4개, +3 -2 라인"));
+        assert!(markdown.contains("| 이름 변경 (90%) | `src/old.js` → `src/new.js` | 1 | 1 |"));
+        assert!(markdown.contains("| 복사 (75%) | `src/base.js` → `src/copy.js` | 0 | 0 |"));
+        assert!(markdown.contains("| 추가 | `assets/logo.png` | 바이너리 | 바이너리 |"));
+        assert!(!markdown.contains("CSV 파일을 참고"));
+    }
+
+    #[test]
+    fn csv_leaves_binary_counts_empty() {
+        let stats = DiffStats::new(&Diff::parse(DIFF.as_bytes()));
+        let path = std::env::temp_dir().join(format!("git-diff-analyzer-stats-{}.csv", std::process::id()));
+        stats.write_csv(&path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            vec![
+                "status,old_path,path,added,removed,similarity",
+                "R,src/old.js,src/new.js,1,1,90",
+                "C,src/base.js,src/copy.js,0,0,75",
+                "A,,assets/logo.png,,,",
+                "M,,src/main.js,2,1,",
+            ]
+        );
+    }
+
+    #[test]
+    fn table_overflow_points_to_csv() {
+        let patch: String = (0..MAX_TABLE_ROWS + 3)
+            .map(|index| format!("diff --git a/f{0}.js b/f{0}.js\n--- a/f{0}.js\n+++ b/f{0}.js\n@@ -1 +1 @@\n-a\n+b\n", index))
+            .collect();
+        let markdown = DiffStats::new(&Diff::parse(patch.as_bytes())).markdown();
+
+        assert_eq!(markdown.matches("| 수정 |").count(), MAX_TABLE_ROWS);
+        assert!(markdown.contains("외 3개 파일은 CSV 파일을 참고해주세요."));
+    }
+}
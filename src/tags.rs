@@ -0,0 +1,294 @@
+use anyhow::{Result, anyhow};
+use log::info;
+use semver::Version;
+
+use crate::repo::RepoBackend;
+use crate::source::DiffSource;
+
+/// 기본 태그 접두사 (`v1.2.3`, `1.2.3`)
+pub const DEFAULT_TAG_PREFIXES: [&str; 2] = ["v", ""];
+
+/// 최신 릴리스 태그를 뜻하는 단축 표기
+const LATEST: &str = "latest";
+/// `--to` 바로 이전 릴리스 태그를 뜻하는 단축 표기 (`--from`에만 사용)
+const PREVIOUS: &str = "previous";
+/// 찾는 태그가 없을 때 앞뒤로 보여줄 태그 수
+const NEAREST_COUNT: usize = 2;
+
+/// 커밋을 가리키는 태그 하나
+pub struct TagInfo {
+    pub name: String,
+    pub sha: String,
+    /// 태그가 가리키는 커밋의 날짜 (YYYY-MM-DD)
+    pub date: String,
+}
+
+impl TagInfo {
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(10)]
+    }
+}
+
+/// 접두사를 떼고 semver로 해석한 릴리스 태그
+pub struct ReleaseTag {
+    pub tag: TagInfo,
+    pub version: Version,
+}
+
+impl ReleaseTag {
+    pub fn is_prerelease(&self) -> bool {
+        !self.version.pre.is_empty()
+    }
+}
+
+/// 태그 이름이나 사용자가 입력한 버전 (`v1.2`처럼 일부만 적은 버전 포함)
+enum VersionSpec {
+    Exact(Version),
+    /// 주 버전(과 부 버전)만 지정
+    Partial { major: u64, minor: Option<u64> },
+}
+
+impl VersionSpec {
+    fn matches(&self, version: &Version) -> bool {
+        match self {
+            VersionSpec::Exact(exact) => version == exact,
+            VersionSpec::Partial { major, minor } => {
+                version.major == *major && minor.is_none_or(|minor| version.minor == minor)
+            }
+        }
+    }
+
+    /// 정렬 위치를 찾을 때 쓰는 버전 (`1.2` → `1.2.0`)
+    fn lower_bound(&self) -> Version {
+        match self {
+            VersionSpec::Exact(exact) => exact.clone(),
+            VersionSpec::Partial { major, minor } => Version::new(*major, minor.unwrap_or(0), 0),
+        }
+    }
+}
+
+/// semver 순으로 정렬한 저장소의 릴리스 태그
+pub struct TagIndex {
+    /// 버전 오름차순 (같은 버전은 이름순)
+    releases: Vec<ReleaseTag>,
+    /// semver로 해석할 수 없어 제외한 태그 수
+    unversioned: usize,
+    /// 긴 접두사부터 시도하도록 정렬한 태그 접두사
+    prefixes: Vec<String>,
+}
+
+impl TagIndex {
+    pub async fn load(repo: &dyn RepoBackend, prefixes: &[String]) -> Result<Self> {
+        Ok(Self::new(repo.list_tags().await?, prefixes))
+    }
+
+    pub fn new(tags: Vec<TagInfo>, prefixes: &[String]) -> Self {
+        let mut prefixes = prefixes.to_vec();
+        prefixes.sort_by_key(|prefix| std::cmp::Reverse(prefix.len()));
+
+        let mut index = Self { releases: Vec::new(), unversioned: 0, prefixes };
+        for tag in tags {
+            match index.parse_spec(&tag.name) {
+                Some(spec) => index.releases.push(ReleaseTag { tag, version: spec.lower_bound() }),
+                None => index.unversioned += 1,
+            }
+        }
+        index.releases.sort_by(|a, b| a.version.cmp(&b.version).then_with(|| a.tag.name.cmp(&b.tag.name)));
+        index
+    }
+
+    /// 버전 오름차순 릴리스 태그
+    pub fn releases(&self) -> &[ReleaseTag] {
+        &self.releases
+    }
+
+    pub fn unversioned(&self) -> usize {
+        self.unversioned
+    }
+
+    /// 태그 접두사 목록 (메시지 출력용)
+    pub fn prefixes_label(&self) -> String {
+        self.prefixes.iter().map(|prefix| format!("\"{}\"", prefix)).collect::<Vec<_>>().join(", ")
+    }
+
+    /// 가장 높은 정식 릴리스 (정식 릴리스가 없으면 가장 높은 사전 릴리스)
+    pub fn latest(&self) -> Option<&ReleaseTag> {
+        self.releases
+            .iter()
+            .rev()
+            .find(|release| !release.is_prerelease())
+            .or_else(|| self.releases.last())
+    }
+
+    /// `to` 태그보다 낮은 가장 높은 릴리스 (`to`가 정식 릴리스면 사전 릴리스는 건너뜀)
+    ///
+    /// `to`가 릴리스 태그가 아니면(`HEAD`, 브랜치 등) 최신 릴리스
+    pub fn previous(&self, to: &str) -> Option<&ReleaseTag> {
+        let Some(to) = self.releases.iter().find(|release| release.tag.name == to) else {
+            return self.latest();
+        };
+        self.releases
+            .iter()
+            .rev()
+            .filter(|release| release.version < to.version)
+            .find(|release| to.is_prerelease() || !release.is_prerelease())
+    }
+
+    /// 태그 이름 또는 버전에 해당하는 릴리스 (`v1.2`처럼 일부만 적으면 해당 시리즈의 첫 릴리스)
+    pub fn find(&self, spec: &str) -> Option<&ReleaseTag> {
+        if let Some(release) = self.releases.iter().find(|release| release.tag.name == spec) {
+            return Some(release);
+        }
+        let spec = self.parse_spec(spec)?;
+        let mut matching = self.releases.iter().filter(|release| spec.matches(&release.version));
+        let first = matching.clone().find(|release| !release.is_prerelease());
+        first.or_else(|| matching.next())
+    }
+
+    /// 태그 이름 또는 버전처럼 보이는지 여부 (찾지 못했을 때 안내 메시지를 보여줄지 판단)
+    fn looks_like_version(&self, spec: &str) -> bool {
+        self.parse_spec(spec).is_some()
+    }
+
+    /// 찾는 버전 앞뒤의 태그 이름 (버전이 아니면 최신 태그들)
+    fn nearest(&self, spec: &str) -> Vec<&str> {
+        let (start, end) = match self.parse_spec(spec) {
+            Some(spec) => {
+                let position = self.releases.partition_point(|release| release.version < spec.lower_bound());
+                (position.saturating_sub(NEAREST_COUNT), (position + NEAREST_COUNT).min(self.releases.len()))
+            }
+            None => (self.releases.len().saturating_sub(NEAREST_COUNT * 2), self.releases.len()),
+        };
+        self.releases[start..end].iter().rev().map(|release| release.tag.name.as_str()).collect()
+    }
+
+    /// 단축 표기나 버전으로 태그를 찾지 못했을 때의 오류
+    fn not_found(&self, spec: &str) -> anyhow::Error {
+        if self.releases.is_empty() {
+            return anyhow!(
+                "'{}'에 해당하는 태그가 없습니다. 태그 접두사 {}로 semver 버전을 해석할 수 있는 태그가 없으니, 설정 파일의 [tags] prefixes나 --tag-prefix로 접두사를 지정해주세요.",
+                spec,
+                self.prefixes_label()
+            );
+        }
+        anyhow!(
+            "'{}'에 해당하는 태그가 없습니다. 가까운 태그: {} (전체 목록은 `git-diff-analyzer tags -p <프로젝트>`로 확인할 수 있습니다)",
+            spec,
+            self.nearest(spec).join(", ")
+        )
+    }
+
+    fn parse_spec(&self, name: &str) -> Option<VersionSpec> {
+        self.prefixes.iter().find_map(|prefix| {
+            let rest = name.strip_prefix(prefix.as_str())?;
+            if let Ok(version) = Version::parse(rest) {
+                return Some(VersionSpec::Exact(version));
+            }
+            // `1`, `1.2`처럼 일부만 적은 버전
+            let mut parts = rest.split('.');
+            let major = parts.next()?.parse().ok()?;
+            let minor = parts.next().map(str::parse).transpose().ok()?;
+            match parts.next() {
+                None => Some(VersionSpec::Partial { major, minor }),
+                Some(_) => None,
+            }
+        })
+    }
+}
+
+/// 리비전 범위의 태그 단축 표기를 실제 태그 이름으로 바꿈
+///
+/// `latest`(최신 릴리스), `previous`(`--to` 이전 릴리스, `--from`에만 사용), 그리고 커밋으로 해석되지 않는
+/// 버전(`v1.2`, `1.2.0` 등)을 semver 순서로 찾음. 버전처럼 보이지만 해당하는 태그가 없으면 가까운 태그를 안내
+pub async fn resolve_tag_shortcuts(repo: &dyn RepoBackend, source: DiffSource, prefixes: &[String]) -> Result<DiffSource> {
+    let DiffSource::Range { from, to, merge_base } = source else {
+        return Ok(source);
+    };
+    if to == PREVIOUS {
+        return Err(anyhow!("'{}'는 이전 리비전(--from)에만 사용할 수 있습니다.", PREVIOUS));
+    }
+
+    let is_shortcut = |rev: &str| rev == LATEST || rev == PREVIOUS;
+    let needs_lookup = is_shortcut(&from)
+        || is_shortcut(&to)
+        || !repo.resolves_to_commit(&from).await?
+        || !repo.resolves_to_commit(&to).await?;
+    if !needs_lookup {
+        return Ok(DiffSource::Range { from, to, merge_base });
+    }
+
+    let index = TagIndex::load(repo, prefixes).await?;
+    let to = resolve_rev(repo, &index, to, None).await?;
+    let from = resolve_rev(repo, &index, from, Some(&to)).await?;
+    Ok(DiffSource::Range { from, to, merge_base })
+}
+
+/// 리비전 하나를 태그 이름으로 바꿈 (`to`는 `previous`의 기준, `--from`일 때만 전달)
+async fn resolve_rev(repo: &dyn RepoBackend, index: &TagIndex, rev: String, to: Option<&str>) -> Result<String> {
+    let release = match (rev.as_str(), to) {
+        (LATEST, _) => index.latest().ok_or_else(|| index.not_found(&rev))?,
+        (PREVIOUS, Some(to)) => index
+            .previous(to)
+            .ok_or_else(|| anyhow!("{} 이전 릴리스 태그가 없습니다.", to))?,
+        _ if repo.resolves_to_commit(&rev).await? => return Ok(rev),
+        _ => match index.find(&rev) {
+            Some(release) => release,
+            None if index.looks_like_version(&rev) => return Err(index.not_found(&rev)),
+            // 태그가 아닌 리비전의 오류는 리비전 확인 단계에서 안내
+            None => return Ok(rev),
+        },
+    };
+    info!("태그 단축 표기 해석: {} → {}", rev, release.tag.name);
+    Ok(release.tag.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(names: &[&str], prefixes: &[&str]) -> TagIndex {
+        let tags = names
+            .iter()
+            .map(|name| TagInfo { name: name.to_string(), sha: String::new(), date: String::new() })
+            .collect();
+        TagIndex::new(tags, &prefixes.iter().map(|prefix| prefix.to_string()).collect::<Vec<_>>())
+    }
+
+    fn spec(index: &TagIndex, name: &str) -> Option<(u64, Option<u64>, Option<u64>)> {
+        index.parse_spec(name).map(|spec| match spec {
+            VersionSpec::Exact(version) => (version.major, Some(version.minor), Some(version.patch)),
+            VersionSpec::Partial { major, minor } => (major, minor, None),
+        })
+    }
+
+    #[test]
+    fn parse_spec_with_prefixes() {
+        let index = index(&[], &DEFAULT_TAG_PREFIXES);
+        assert_eq!(spec(&index, "v1.2.3"), Some((1, Some(2), Some(3))));
+        assert_eq!(spec(&index, "1.2.3"), Some((1, Some(2), Some(3))));
+        assert_eq!(spec(&index, "v1.2"), Some((1, Some(2), None)));
+        assert_eq!(spec(&index, "2"), Some((2, None, None)));
+        assert_eq!(spec(&index, "v1.2.3-rc.1").map(|(major, ..)| major), Some(1));
+        assert_eq!(spec(&index, "release-1.0"), None);
+        assert_eq!(spec(&index, "v1.x"), None);
+        assert_eq!(spec(&index, "main"), None);
+
+        let index = self::index(&[], &["player-v"]);
+        assert_eq!(spec(&index, "player-v2.0.0"), Some((2, Some(0), Some(0))));
+        assert_eq!(spec(&index, "v2.0.0"), None);
+    }
+
+    #[test]
+    fn orders_releases_by_semver() {
+        let index = index(&["v1.10.0", "v1.2.0", "v2.0.0-rc.1", "v1.9.0", "nightly"], &DEFAULT_TAG_PREFIXES);
+        let names: Vec<&str> = index.releases().iter().map(|release| release.tag.name.as_str()).collect();
+        assert_eq!(names, ["v1.2.0", "v1.9.0", "v1.10.0", "v2.0.0-rc.1"]);
+        assert_eq!(index.unversioned(), 1);
+        assert_eq!(index.latest().map(|release| release.tag.name.as_str()), Some("v1.10.0"));
+        assert_eq!(index.previous("v1.10.0").map(|release| release.tag.name.as_str()), Some("v1.9.0"));
+        assert_eq!(index.previous("HEAD").map(|release| release.tag.name.as_str()), Some("v1.10.0"));
+        assert_eq!(index.find("v1").map(|release| release.tag.name.as_str()), Some("v1.2.0"));
+        assert_eq!(index.find("2").map(|release| release.tag.name.as_str()), Some("v2.0.0-rc.1"));
+        assert!(index.find("v3").is_none());
+    }
+}
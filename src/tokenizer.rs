@@ -0,0 +1,50 @@
+use tiktoken_rs::tokenizer::{Tokenizer, get_tokenizer};
+use tiktoken_rs::{CoreBPE, cl100k_base_singleton, o200k_base_singleton};
+
+/// tiktoken 모델 표에 아직 없는 o200k 계열 모델 이름 접두사
+const O200K_MODEL_PREFIXES: &[&str] = &["gpt-5", "gpt-4.5", "o1", "o3", "o4"];
+
+/// 모델에 맞는 BPE 인코딩으로 토큰 수를 계산
+///
+/// OpenAI 모델은 해당 모델의 인코딩(cl100k/o200k)을 사용하고, 그 외 모델(Claude, Llama 등)은
+/// 공개된 토크나이저가 없으므로 cl100k_base로 근사함. 어휘 파일은 바이너리에 포함되어 있어
+/// 네트워크 없이 동작함.
+#[derive(Clone, Copy)]
+pub struct TokenCounter {
+    bpe: &'static CoreBPE,
+    encoding: &'static str,
+    /// 모델의 실제 토크나이저와 같은 인코딩인지 여부
+    exact: bool,
+}
+
+impl TokenCounter {
+    pub fn for_model(model: &str) -> Self {
+        // 제공자 접두사가 붙은 이름(openai/gpt-4o 등)도 처리
+        let name = model.rsplit('/').next().unwrap_or(model);
+        let tokenizer = get_tokenizer(name).or_else(|| {
+            O200K_MODEL_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+                .then_some(Tokenizer::O200kBase)
+        });
+
+        match tokenizer {
+            Some(Tokenizer::O200kBase) => Self { bpe: o200k_base_singleton(), encoding: "o200k_base", exact: true },
+            Some(Tokenizer::Cl100kBase) => Self { bpe: cl100k_base_singleton(), encoding: "cl100k_base", exact: true },
+            _ => Self { bpe: cl100k_base_singleton(), encoding: "cl100k_base", exact: false },
+        }
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        self.bpe.encode_ordinary(text).len()
+    }
+
+    /// 토큰 사용량 출력에 붙이는 인코딩 설명
+    pub fn label(&self) -> String {
+        if self.exact {
+            self.encoding.to_string()
+        } else {
+            format!("{} 근사치", self.encoding)
+        }
+    }
+}